@@ -1,12 +1,13 @@
 //! 2D and 3D camera.
 
 use crate::{
-    get_context,
-    math::Rect,
+    get_context, get_quad_context,
+    math::{Plane, Rect},
+    models::Mesh,
     texture::RenderTarget,
     window::{screen_height, screen_width},
 };
-use glam::{vec2, vec3, Mat4, Vec2, Vec3};
+use glam::{vec2, vec3, Mat3, Mat4, Quat, Vec2, Vec3};
 
 pub trait Camera {
     fn matrix(&self) -> Mat4;
@@ -20,6 +21,11 @@ pub struct Camera2D {
     /// Rotation in degrees
     pub rotation: f32,
     /// Scaling, should be (1.0, 1.0) by default
+    ///
+    /// Camera space is mapped to the screen as `(point - target) * zoom + offset`,
+    /// with the screen going from -1 to 1, so with zoom (1.0, 1.0) the screen is 2 units wide.
+    /// `zoom: vec2(1. / 400., 1. / 400.)` makes the screen 800 units wide,
+    /// see [Camera2D::with_pixels_per_unit] for specifying zoom in screen pixels.
     pub zoom: Vec2,
     /// Rotation and zoom origin
     pub target: Vec2,
@@ -28,6 +34,11 @@ pub struct Camera2D {
 
     /// If "render_target" is set - camera will render to texture
     /// otherwise to the screen
+    ///
+    /// Texture rows go bottom to top, opposite to the screen.
+    /// Content drawn with a Y-down camera, like [Camera2D::from_display_rect],
+    /// will be upside down when the texture is drawn with `draw_texture`.
+    /// Use positive `zoom.y` for render targets or `DrawTextureParams::flip_y`.
     pub render_target: Option<RenderTarget>,
 
     /// Part of the screen to render to
@@ -37,6 +48,9 @@ pub struct Camera2D {
     /// Viewport do not affect camera space, just the render position on the screen
     ///
     /// Useful for things like splitscreen
+    ///
+    /// In physical pixels with the origin in the bottom left corner,
+    /// multiply logical sizes by `screen_dpi_scale()`
     pub viewport: Option<(i32, i32, i32, i32)>,
 }
 
@@ -55,6 +69,97 @@ impl Camera2D {
             viewport: None,
         }
     }
+
+    /// Camera showing the whole `rect` on a screen of `screen_size`, with Y going down,
+    /// without stretching: the rect is centered and extended along one axis
+    /// to match the screen aspect ratio.
+    /// Same as [Camera2D::from_display_rect] when aspect ratios already match.
+    pub fn fit_rect(rect: Rect, screen_size: Vec2) -> Camera2D {
+        let screen_aspect = screen_size.x / screen_size.y;
+        let (w, h) = if rect.w / rect.h > screen_aspect {
+            (rect.w, rect.w / screen_aspect)
+        } else {
+            (rect.h * screen_aspect, rect.h)
+        };
+        let center = rect.center();
+
+        Camera2D::from_display_rect(Rect::new(center.x - w / 2., center.y - h / 2., w, h))
+    }
+
+    /// Like [Camera2D::from_display_rect], but keeps the rect aspect ratio:
+    /// the camera renders into the biggest centered viewport of the rect aspect
+    /// that fits the screen, leaving bars on the sides or on top and bottom.
+    ///
+    /// Bars are not drawn into and keep the `clear_background` color.
+    /// Depends on the current screen size, so should be re-created when the window is resized.
+    pub fn letterboxed(rect: Rect) -> Camera2D {
+        let (screen_width, screen_height) = get_quad_context().screen_size();
+        let scale = (screen_width / rect.w).min(screen_height / rect.h);
+        let (width, height) = ((rect.w * scale) as i32, (rect.h * scale) as i32);
+
+        Camera2D {
+            viewport: Some((
+                (screen_width as i32 - width) / 2,
+                (screen_height as i32 - height) / 2,
+                width,
+                height,
+            )),
+            ..Camera2D::from_display_rect(rect)
+        }
+    }
+
+    /// Camera for pixel art games: camera space is (0, 0)..(width, height) with Y going down,
+    /// scaled by the biggest integer factor that fits the screen and centered on it.
+    ///
+    /// One logical pixel always covers the same amount of screen pixels
+    /// and the camera space origin lands exactly on a screen pixel, so sprites do not shimmer.
+    /// Textures should use `FilterMode::Nearest`.
+    ///
+    /// `screen_size` is in physical pixels, `vec2(screen_width(), screen_height()) * screen_dpi_scale()`,
+    /// with a fractional DPI scale logical pixels are not on the physical pixel grid.
+    /// Should be re-created when the window is resized.
+    pub fn pixel_perfect(width: f32, height: f32, screen_size: Vec2) -> Camera2D {
+        let (screen_width, screen_height) = (screen_size.x, screen_size.y);
+        let scale = (screen_width / width)
+            .min(screen_height / height)
+            .floor()
+            .max(1.);
+
+        // top left corner of the camera space, in whole screen pixels
+        let left = ((screen_width - width * scale) / 2.).floor();
+        let top = ((screen_height - height * scale) / 2.).floor();
+
+        Camera2D {
+            target: vec2(
+                (screen_width / 2. - left) / scale,
+                (screen_height / 2. - top) / scale,
+            ),
+            zoom: vec2(scale * 2. / screen_width, -scale * 2. / screen_height),
+            offset: vec2(0., 0.),
+            rotation: 0.,
+
+            render_target: None,
+            viewport: None,
+        }
+    }
+
+    /// Camera centered on `target` where one camera space unit takes `ppu` screen pixels,
+    /// with Y going up.
+    ///
+    /// Zoom depends on the current screen size,
+    /// so camera should be re-created when the window is resized.
+    pub fn with_pixels_per_unit(target: Vec2, ppu: f32) -> Camera2D {
+        Camera2D {
+            target,
+            zoom: vec2(ppu * 2. / screen_width(), ppu * 2. / screen_height()),
+            ..Default::default()
+        }
+    }
+
+    /// How many screen pixels one camera space unit takes horizontally with the current zoom.
+    pub fn pixels_per_unit(&self) -> f32 {
+        self.zoom.x * screen_width() / 2.
+    }
 }
 
 impl Default for Camera2D {
@@ -111,8 +216,20 @@ impl Camera for Camera2D {
 }
 
 impl Camera2D {
+    /// 2D camera has no projection, the whole transform is in `matrix()`.
+    /// Kept for symmetry with [Camera3D::view_matrix].
+    pub fn view_matrix(&self) -> Mat4 {
+        self.matrix()
+    }
+
+    /// Always identity, see [Camera2D::view_matrix].
+    pub fn projection_matrix(&self) -> Mat4 {
+        Mat4::IDENTITY
+    }
+
     /// Returns the screen space position for a 2d camera world space position
     /// Screen position in window space - from (0, 0) to (screen_width, screen_height())
+    /// Logical pixels, the same as `mouse_position()`, not affected by DPI scale
     pub fn world_to_screen(&self, point: Vec2) -> Vec2 {
         let mat = self.matrix();
         let transform = mat.transform_point3(vec3(point.x, point.y, 0.));
@@ -123,8 +240,8 @@ impl Camera2D {
         )
     }
 
-    // Returns the world space position for a 2d camera screen space position
-    // Point is a screen space position, often mouse x and y
+    /// Returns the world space position for a 2d camera screen space position
+    /// Point is a screen space position in logical pixels, often mouse x and y
     pub fn screen_to_world(&self, point: Vec2) -> Vec2 {
         let point = vec2(
             point.x / screen_width() * 2. - 1.,
@@ -135,6 +252,27 @@ impl Camera2D {
 
         vec2(transform.x, transform.y)
     }
+
+    /// World space rectangle visible through the camera.
+    /// With a rotated camera this is the bounding box of the visible area.
+    /// Useful to skip drawing objects that do not overlap it,
+    /// for example off-screen tiles of a big tilemap.
+    pub fn view_rect(&self) -> Rect {
+        let inv_mat = self.matrix().inverse();
+        let corners = [vec2(-1., -1.), vec2(1., -1.), vec2(1., 1.), vec2(-1., 1.)]
+            .iter()
+            .map(|corner| inv_mat.transform_point3(corner.extend(0.)).truncate())
+            .collect::<Vec<_>>();
+
+        let min = corners
+            .iter()
+            .fold(corners[0], |min, corner| min.min(*corner));
+        let max = corners
+            .iter()
+            .fold(corners[0], |max, corner| max.max(*corner));
+
+        Rect::new(min.x, min.y, max.x - min.x, max.y - min.y)
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -168,6 +306,8 @@ pub struct Camera3D {
     /// None means the whole screen
     /// Viewport do not affect camera space, just the render position on the screen
     /// Useful for things like splitscreen
+    /// In physical pixels with the origin in the bottom left corner,
+    /// multiply logical sizes by `screen_dpi_scale()`
     pub viewport: Option<(i32, i32, i32, i32)>,
 }
 
@@ -189,26 +329,134 @@ impl Default for Camera3D {
 impl Camera3D {
     const Z_NEAR: f32 = 0.01;
     const Z_FAR: f32 = 10000.0;
-}
 
-impl Camera for Camera3D {
-    fn matrix(&self) -> Mat4 {
-        let aspect = self.aspect.unwrap_or(screen_width() / screen_height());
+    /// Camera at `position`, oriented by `rotation`, all other fields are default.
+    ///
+    /// With identity rotation camera looks along -Z with +Y up.
+    /// Keeping the orientation as a quaternion allows rolling the camera
+    /// and accumulating rotations without gimbal lock, useful for flight-sim like cameras.
+    pub fn from_orientation(position: Vec3, rotation: Quat) -> Camera3D {
+        let mut camera = Camera3D {
+            position,
+            ..Default::default()
+        };
+        camera.set_orientation(rotation);
+
+        camera
+    }
+
+    /// Point camera by `rotation`, keeping its position.
+    /// Updates `target` and `up`, see [Camera3D::from_orientation].
+    pub fn set_orientation(&mut self, rotation: Quat) {
+        self.target = self.position + rotation * Vec3::NEG_Z;
+        self.up = rotation * Vec3::Y;
+    }
+
+    /// Rotation that points the camera from identity orientation (looking along -Z, +Y up)
+    /// towards `target` with `up`. Inverse of [Camera3D::set_orientation].
+    pub fn orientation(&self) -> Quat {
+        let forward = (self.target - self.position).normalize_or_zero();
+        if forward == Vec3::ZERO {
+            return Quat::IDENTITY;
+        }
+        let mut right = forward.cross(self.up).normalize_or_zero();
+        if right == Vec3::ZERO {
+            // up is parallel to the view direction, any roll will do
+            right = forward.any_orthonormal_vector();
+        }
+        let up = right.cross(forward);
+
+        Quat::from_mat3(&Mat3::from_cols(right, up, -forward)).normalize()
+    }
+
+    /// Move the camera to look at the whole `mesh` along `direction`.
+    ///
+    /// Keeps `fovy`, `aspect`, `projection` and, when possible, `up`.
+    /// For orthographic projection `fovy` is changed to fit the mesh instead of the distance.
+    pub fn frame_mesh(&mut self, mesh: &Mesh, direction: Vec3) {
+        let (min, max) = match mesh.bounding_box() {
+            Some(bounding_box) => bounding_box,
+            None => return,
+        };
+        let center = (min + max) / 2.;
+        let radius = mesh
+            .vertices
+            .iter()
+            .map(|vertex| vertex.position.distance(center))
+            .fold(0., f32::max)
+            .max(Self::Z_NEAR);
+
+        let direction = match direction.try_normalize() {
+            Some(direction) => direction,
+            None => (self.target - self.position).normalize_or_zero(),
+        };
+        let direction = if direction == Vec3::ZERO {
+            Vec3::NEG_Z
+        } else {
+            direction
+        };
+        if self.up.cross(direction).length_squared() < f32::EPSILON {
+            self.up = direction.any_orthonormal_vector();
+        }
+
+        let aspect = self
+            .aspect
+            .unwrap_or_else(|| screen_width() / screen_height());
+        let distance = match self.projection {
+            Projection::Perspective => {
+                // the same fovy interpretation as in `matrix`
+                let tan_y = (self.fovy / 2.).tan().abs();
+                let half_fov = tan_y.atan().min((tan_y * aspect).atan());
+
+                radius / half_fov.sin()
+            }
+            Projection::Orthographics => {
+                self.fovy = radius * 2. / aspect.min(1.);
+
+                radius * 2.
+            }
+        };
+
+        self.target = center;
+        self.position = center - direction * distance;
+    }
+
+    /// World to camera space transform, without the projection.
+    /// `matrix()` is `projection_matrix() * view_matrix()`.
+    pub fn view_matrix(&self) -> Mat4 {
+        Mat4::look_at_rh(self.position, self.target, self.up)
+    }
+
+    /// Six planes bounding the visible volume, facing inwards,
+    /// see [Plane::frustum]. Useful for culling and visibility tests.
+    pub fn frustum_planes(&self) -> [Plane; 6] {
+        Plane::frustum(self.matrix())
+    }
+
+    /// Camera space to clip space transform.
+    pub fn projection_matrix(&self) -> Mat4 {
+        let aspect = self
+            .aspect
+            .unwrap_or_else(|| screen_width() / screen_height());
 
         match self.projection {
             Projection::Perspective => {
                 Mat4::perspective_rh_gl(self.fovy, aspect, Self::Z_NEAR, Self::Z_FAR)
-                    * Mat4::look_at_rh(self.position, self.target, self.up)
             }
             Projection::Orthographics => {
                 let top = self.fovy / 2.0;
                 let right = top * aspect;
 
                 Mat4::orthographic_rh_gl(-right, right, -top, top, Self::Z_NEAR, Self::Z_FAR)
-                    * Mat4::look_at_rh(self.position, self.target, self.up)
             }
         }
     }
+}
+
+impl Camera for Camera3D {
+    fn matrix(&self) -> Mat4 {
+        self.projection_matrix() * self.view_matrix()
+    }
 
     fn depth_enabled(&self) -> bool {
         true
@@ -244,35 +492,198 @@ pub fn set_default_camera() {
     context.perform_render_passes();
 
     context.gl.render_pass(None);
+    context.gl.viewport(None);
     context.gl.depth_test(false);
     context.camera_matrix = None;
 }
 
+/// Is the active camera rendering into a render target, not the screen.
+///
+/// Render targets have Y going the opposite way from the screen,
+/// see [Camera2D::render_target].
+pub fn is_render_target_active() -> bool {
+    let context = get_context();
+
+    context.gl.get_active_render_pass().is_some()
+}
+
 pub(crate) struct CameraState {
     render_pass: Option<miniquad::RenderPass>,
+    viewport: Option<(i32, i32, i32, i32)>,
     depth_test: bool,
     matrix: Option<Mat4>,
 }
 
+/// Save the active camera: its matrix, render target, viewport and depth test.
+/// Should be paired with [pop_camera_state], handy for drawing nested layers
+/// with their own cameras.
 pub fn push_camera_state() {
     let context = get_context();
 
     let camera_state = CameraState {
         render_pass: context.gl.get_active_render_pass(),
+        viewport: context.gl.get_active_viewport(),
         depth_test: context.gl.is_depth_test_enabled(),
         matrix: context.camera_matrix,
     };
     context.camera_stack.push(camera_state);
 }
 
+/// Restore the camera saved by the last [push_camera_state].
+/// Panics in debug builds when there is nothing to restore.
 pub fn pop_camera_state() {
     let context = get_context();
 
+    debug_assert!(
+        !context.camera_stack.is_empty(),
+        "pop_camera_state without a matching push_camera_state"
+    );
+
     if let Some(camera_state) = context.camera_stack.pop() {
         context.perform_render_passes();
 
         context.gl.render_pass(camera_state.render_pass);
+        context.gl.viewport(camera_state.viewport);
         context.gl.depth_test(camera_state.depth_test);
         context.camera_matrix = camera_state.matrix;
     }
 }
+
+#[test]
+fn camera2d_fit_rect() {
+    let near = |a: Vec3, b: Vec3| (a - b).length() < 0.0001;
+
+    // same aspect, rect corners are screen corners
+    let camera = Camera2D::fit_rect(Rect::new(10., 20., 400., 300.), vec2(800., 600.));
+    let matrix = camera.matrix();
+    assert!(near(
+        matrix.transform_point3(vec3(10., 20., 0.)),
+        vec3(-1., 1., 0.)
+    ));
+    assert!(near(
+        matrix.transform_point3(vec3(410., 320., 0.)),
+        vec3(1., -1., 0.)
+    ));
+
+    // wider screen, rect fills the height and is centered horizontally
+    let camera = Camera2D::fit_rect(Rect::new(0., 0., 100., 100.), vec2(200., 100.));
+    let matrix = camera.matrix();
+    assert!(near(
+        matrix.transform_point3(vec3(0., 0., 0.)),
+        vec3(-0.5, 1., 0.)
+    ));
+    assert!(near(
+        matrix.transform_point3(vec3(100., 100., 0.)),
+        vec3(0.5, -1., 0.)
+    ));
+}
+
+#[test]
+fn camera2d_view_rect() {
+    let near = |a: Rect, b: Rect| {
+        (a.point() - b.point()).length() < 0.0001 && (a.size() - b.size()).length() < 0.0001
+    };
+
+    let rect = Rect::new(-50., 10., 400., 300.);
+    let camera = Camera2D::from_display_rect(rect);
+    // display rect flips y, the visible area is the same rect
+    assert!(near(camera.view_rect(), rect));
+
+    let camera = Camera2D {
+        rotation: 90.,
+        ..camera
+    };
+    assert!(near(camera.view_rect(), Rect::new(0., -40., 300., 400.)));
+}
+
+#[test]
+fn camera2d_pixel_perfect() {
+    // 800x600 logical window at 1.25 DPI scale, and an odd sized one
+    for &(screen_width, screen_height) in &[(1000., 750.), (1001., 751.)] {
+        let camera = Camera2D::pixel_perfect(320., 180., vec2(screen_width, screen_height));
+        let matrix = camera.matrix();
+
+        for &corner in &[vec2(0., 0.), vec2(320., 180.), vec2(1., 1.)] {
+            let ndc = matrix.transform_point3(corner.extend(0.));
+            let x = (ndc.x / 2. + 0.5) * screen_width;
+            let y = (0.5 - ndc.y / 2.) * screen_height;
+            assert!((x - x.round()).abs() < 0.001, "{} {}", x, y);
+            assert!((y - y.round()).abs() < 0.001, "{} {}", x, y);
+        }
+    }
+}
+
+#[test]
+fn camera3d_orientation() {
+    let position = vec3(1., 2., 3.);
+    for &rotation in &[
+        Quat::IDENTITY,
+        Quat::from_rotation_y(1.),
+        Quat::from_rotation_x(-0.7) * Quat::from_rotation_z(2.5),
+        Quat::from_axis_angle(vec3(1., 1., -2.).normalize(), 3.),
+    ] {
+        let camera = Camera3D::from_orientation(position, rotation);
+        assert!((camera.target - position - rotation * Vec3::NEG_Z).length() < 0.0001);
+        assert!(camera.orientation().dot(rotation).abs() > 0.9999);
+    }
+
+    // up parallel to the view direction, roll is arbitrary but forward is kept
+    let camera = Camera3D {
+        position,
+        target: position - Vec3::Y,
+        up: Vec3::Y,
+        ..Default::default()
+    };
+    let orientation = camera.orientation();
+    assert!(orientation.is_finite());
+    assert!((orientation.length() - 1.).abs() < 0.0001);
+    assert!((orientation * Vec3::NEG_Z - Vec3::NEG_Y).length() < 0.0001);
+}
+
+#[test]
+fn camera3d_frame_mesh() {
+    use crate::{color::WHITE, models::Vertex};
+
+    let mesh = Mesh {
+        vertices: [
+            vec3(10., 2., -3.),
+            vec3(14., 2.5, -3.),
+            vec3(12., 7., -1.),
+            vec3(11., 3., 4.),
+            vec3(13.5, 6., 2.),
+        ]
+        .iter()
+        .map(|&position| Vertex {
+            position,
+            uv: vec2(0., 0.),
+            color: WHITE,
+        })
+        .collect(),
+        indices: vec![],
+        texture: None,
+    };
+    assert_eq!(
+        mesh.bounding_box(),
+        Some((vec3(10., 2., -3.), vec3(14., 7., 4.)))
+    );
+
+    for &projection in &[Projection::Perspective, Projection::Orthographics] {
+        for &aspect in &[16. / 9., 0.5] {
+            let mut camera = Camera3D {
+                fovy: 1.,
+                aspect: Some(aspect),
+                projection,
+                up: Vec3::Y,
+                ..Default::default()
+            };
+            camera.frame_mesh(&mesh, vec3(1., -0.5, -2.));
+
+            let planes = Plane::frustum(camera.matrix());
+            for vertex in &mesh.vertices {
+                for plane in &planes {
+                    assert!(plane.distance(vertex.position) > -0.0001);
+                }
+            }
+        }
+    }
+}
@@ -39,6 +39,25 @@ fn color_from_bytes() {
     );
 }
 
+#[test]
+fn color_named() {
+    assert_eq!(Color::named("red"), Some(RED));
+    assert_eq!(Color::named("Dark Gray"), Some(DARKGRAY));
+    assert_eq!(Color::named("SKY_BLUE"), Some(SKYBLUE));
+    assert_eq!(
+        Color::named("#FF0000"),
+        Some(Color::new(1.0, 0.0, 0.0, 1.0))
+    );
+    assert_eq!(Color::named("#FF00"), None);
+    assert_eq!(Color::named("not a color"), None);
+
+    assert_eq!(Color::named("#+12345"), None);
+    assert_eq!(Color::named("coral"), Some(CORAL));
+    for (name, color) in colors::NAMED {
+        assert_eq!(Color::named(name), Some(*color));
+    }
+}
+
 impl Into<[u8; 4]> for Color {
     fn into(self) -> [u8; 4] {
         [
@@ -98,6 +117,35 @@ impl Color {
         Self::from_rgba(bytes[1], bytes[2], bytes[3], 255)
     }
 
+    /// Find a color by the name of one of the [colors] constants, or by "#RRGGBB" hex code.
+    /// Case, spaces, dashes and underscores are ignored, so "Dark Gray" and "DARK_GRAY" are DARKGRAY.
+    ///
+    /// Useful for colors coming from config files.
+    ///
+    /// Names shared with CSS are not always CSS colors: the original palette, like
+    /// RED, GREEN, BLUE or GRAY, follows raylib, so "red" is (0.90, 0.16, 0.22).
+    /// Names added later, like "coral" or "indigo", use the CSS values.
+    pub fn named(name: &str) -> Option<Color> {
+        let name = name.trim();
+        if let Some(hex) = name.strip_prefix('#') {
+            if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+                return None;
+            }
+            return u32::from_str_radix(hex, 16).ok().map(Color::from_hex);
+        }
+
+        let name: String = name
+            .chars()
+            .filter(|c| !matches!(c, ' ' | '-' | '_'))
+            .map(|c| c.to_ascii_lowercase())
+            .collect();
+
+        colors::NAMED
+            .iter()
+            .find(|(color_name, _)| color_name.eq_ignore_ascii_case(&name))
+            .map(|(_, color)| *color)
+    }
+
     pub fn to_vec(&self) -> glam::Vec4 {
         glam::Vec4::new(self.r, self.g, self.b, self.a)
     }
@@ -112,31 +160,69 @@ pub mod colors {
 
     use super::Color;
 
-    pub const LIGHTGRAY: Color = Color::new(0.78, 0.78, 0.78, 1.00);
-    pub const GRAY: Color = Color::new(0.51, 0.51, 0.51, 1.00);
-    pub const DARKGRAY: Color = Color::new(0.31, 0.31, 0.31, 1.00);
-    pub const YELLOW: Color = Color::new(0.99, 0.98, 0.00, 1.00);
-    pub const GOLD: Color = Color::new(1.00, 0.80, 0.00, 1.00);
-    pub const ORANGE: Color = Color::new(1.00, 0.63, 0.00, 1.00);
-    pub const PINK: Color = Color::new(1.00, 0.43, 0.76, 1.00);
-    pub const RED: Color = Color::new(0.90, 0.16, 0.22, 1.00);
-    pub const MAROON: Color = Color::new(0.75, 0.13, 0.22, 1.00);
-    pub const GREEN: Color = Color::new(0.00, 0.89, 0.19, 1.00);
-    pub const LIME: Color = Color::new(0.00, 0.62, 0.18, 1.00);
-    pub const DARKGREEN: Color = Color::new(0.00, 0.46, 0.17, 1.00);
-    pub const SKYBLUE: Color = Color::new(0.40, 0.75, 1.00, 1.00);
-    pub const BLUE: Color = Color::new(0.00, 0.47, 0.95, 1.00);
-    pub const DARKBLUE: Color = Color::new(0.00, 0.32, 0.67, 1.00);
-    pub const PURPLE: Color = Color::new(0.78, 0.48, 1.00, 1.00);
-    pub const VIOLET: Color = Color::new(0.53, 0.24, 0.75, 1.00);
-    pub const DARKPURPLE: Color = Color::new(0.44, 0.12, 0.49, 1.00);
-    pub const BEIGE: Color = Color::new(0.83, 0.69, 0.51, 1.00);
-    pub const BROWN: Color = Color::new(0.50, 0.42, 0.31, 1.00);
-    pub const DARKBROWN: Color = Color::new(0.30, 0.25, 0.18, 1.00);
-    pub const WHITE: Color = Color::new(1.00, 1.00, 1.00, 1.00);
-    pub const BLACK: Color = Color::new(0.00, 0.00, 0.00, 1.00);
-    pub const BLANK: Color = Color::new(0.00, 0.00, 0.00, 0.00);
-    pub const MAGENTA: Color = Color::new(1.00, 0.00, 1.00, 1.00);
+    // Defines the constants and the `NAMED` table used by `Color::named` from one list.
+    macro_rules! named_colors {
+        ($($name:ident = ($r:expr, $g:expr, $b:expr, $a:expr);)*) => {
+            $(pub const $name: Color = Color::new($r, $g, $b, $a);)*
+
+            /// All the constants above by their names, used by `Color::named`
+            pub(crate) const NAMED: &[(&str, Color)] = &[$((stringify!($name), $name)),*];
+        };
+    }
+
+    named_colors! {
+        LIGHTGRAY = (0.78, 0.78, 0.78, 1.00);
+        GRAY = (0.51, 0.51, 0.51, 1.00);
+        DARKGRAY = (0.31, 0.31, 0.31, 1.00);
+        YELLOW = (0.99, 0.98, 0.00, 1.00);
+        GOLD = (1.00, 0.80, 0.00, 1.00);
+        ORANGE = (1.00, 0.63, 0.00, 1.00);
+        PINK = (1.00, 0.43, 0.76, 1.00);
+        RED = (0.90, 0.16, 0.22, 1.00);
+        MAROON = (0.75, 0.13, 0.22, 1.00);
+        GREEN = (0.00, 0.89, 0.19, 1.00);
+        LIME = (0.00, 0.62, 0.18, 1.00);
+        DARKGREEN = (0.00, 0.46, 0.17, 1.00);
+        SKYBLUE = (0.40, 0.75, 1.00, 1.00);
+        BLUE = (0.00, 0.47, 0.95, 1.00);
+        DARKBLUE = (0.00, 0.32, 0.67, 1.00);
+        PURPLE = (0.78, 0.48, 1.00, 1.00);
+        VIOLET = (0.53, 0.24, 0.75, 1.00);
+        DARKPURPLE = (0.44, 0.12, 0.49, 1.00);
+        BEIGE = (0.83, 0.69, 0.51, 1.00);
+        BROWN = (0.50, 0.42, 0.31, 1.00);
+        DARKBROWN = (0.30, 0.25, 0.18, 1.00);
+        WHITE = (1.00, 1.00, 1.00, 1.00);
+        BLACK = (0.00, 0.00, 0.00, 1.00);
+        BLANK = (0.00, 0.00, 0.00, 0.00);
+        MAGENTA = (1.00, 0.00, 1.00, 1.00);
+        CYAN = (0.00, 1.00, 1.00, 1.00);
+        TEAL = (0.00, 0.50, 0.50, 1.00);
+        NAVY = (0.00, 0.00, 0.50, 1.00);
+        OLIVE = (0.50, 0.50, 0.00, 1.00);
+        SILVER = (0.75, 0.75, 0.75, 1.00);
+        CORAL = (1.00, 0.50, 0.31, 1.00);
+        SALMON = (0.98, 0.50, 0.45, 1.00);
+        CRIMSON = (0.86, 0.08, 0.24, 1.00);
+        TOMATO = (1.00, 0.39, 0.28, 1.00);
+        CHOCOLATE = (0.82, 0.41, 0.12, 1.00);
+        SIENNA = (0.63, 0.32, 0.18, 1.00);
+        TAN = (0.82, 0.71, 0.55, 1.00);
+        KHAKI = (0.94, 0.90, 0.55, 1.00);
+        IVORY = (1.00, 1.00, 0.94, 1.00);
+        CHARTREUSE = (0.50, 1.00, 0.00, 1.00);
+        FORESTGREEN = (0.13, 0.55, 0.13, 1.00);
+        SEAGREEN = (0.18, 0.55, 0.34, 1.00);
+        AQUAMARINE = (0.50, 1.00, 0.83, 1.00);
+        TURQUOISE = (0.25, 0.88, 0.82, 1.00);
+        STEELBLUE = (0.27, 0.51, 0.71, 1.00);
+        ROYALBLUE = (0.25, 0.41, 0.88, 1.00);
+        SLATEGRAY = (0.44, 0.50, 0.56, 1.00);
+        INDIGO = (0.29, 0.00, 0.51, 1.00);
+        LAVENDER = (0.90, 0.90, 0.98, 1.00);
+        ORCHID = (0.85, 0.44, 0.84, 1.00);
+        PLUM = (0.87, 0.63, 0.87, 1.00);
+    }
 }
 
 #[rustfmt::skip]
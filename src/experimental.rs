@@ -6,5 +6,7 @@ pub mod animation;
 pub mod camera;
 pub mod collections;
 pub mod coroutines;
+pub mod debug_overlay;
 pub mod scene;
 pub mod state_machine;
+pub mod tween;
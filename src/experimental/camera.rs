@@ -1 +1,2 @@
+pub mod fov;
 pub mod mouse;
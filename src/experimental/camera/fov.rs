@@ -0,0 +1,94 @@
+use crate::prelude::*;
+
+/// Smoothly changes `Camera3D::fovy` towards a target value, for aiming and scope zoom.
+///
+/// Smoothing is exponential and does not depend on the frame rate.
+#[derive(Debug, Copy, Clone)]
+pub struct FovController {
+    /// Current field of view
+    pub fovy: f32,
+    /// Field of view `fovy` is moving towards
+    pub target: f32,
+    /// Smallest allowed field of view, the most zoomed in
+    pub min: f32,
+    /// Biggest allowed field of view, the most zoomed out
+    pub max: f32,
+    /// How fast `fovy` catches up with `target`, bigger is faster.
+    /// Roughly the inverse of the time in seconds to cover 63% of the distance.
+    pub speed: f32,
+}
+
+impl FovController {
+    /// Controller starting at `fovy`, limited to [min, max].
+    /// Panics if `min` is bigger than `max`.
+    pub fn new(fovy: f32, min: f32, max: f32) -> Self {
+        assert!(
+            min <= max,
+            "FovController min {} is bigger than max {}",
+            min,
+            max
+        );
+        let fovy = clamp(fovy, min, max);
+
+        Self {
+            fovy,
+            target: fovy,
+            min,
+            max,
+            speed: 10.,
+        }
+    }
+
+    /// Set new target, clamped to [min, max]
+    pub fn set_target(&mut self, target: f32) {
+        self.target = clamp(target, self.min, self.max);
+    }
+
+    /// Jump straight to `fovy`, without smoothing
+    pub fn snap(&mut self, fovy: f32) {
+        self.fovy = clamp(fovy, self.min, self.max);
+        self.target = self.fovy;
+    }
+
+    /// Move `fovy` towards the target by the last frame time and apply it to `camera`.
+    /// Should be called every frame.
+    pub fn update(&mut self, camera: &mut Camera3D) {
+        self.advance(get_frame_time());
+
+        camera.fovy = self.fovy;
+    }
+
+    /// Move `fovy` towards the target by `dt` seconds.
+    pub fn advance(&mut self, dt: f32) {
+        let t = 1. - (-self.speed * dt).exp();
+
+        self.fovy = clamp(
+            self.fovy + (self.target - self.fovy) * t,
+            self.min,
+            self.max,
+        );
+    }
+}
+
+#[test]
+fn fov_frame_rate_independent() {
+    let mut fast = FovController::new(1., 0.2, 1.5);
+    let mut slow = fast;
+    fast.set_target(0.3);
+    slow.set_target(0.3);
+
+    for _ in 0..60 {
+        fast.advance(1. / 60.);
+    }
+    for _ in 0..30 {
+        slow.advance(1. / 30.);
+    }
+    assert!((fast.fovy - slow.fovy).abs() < 0.0001);
+    assert!(fast.fovy < 0.31 && fast.fovy > 0.3);
+}
+
+#[test]
+#[should_panic]
+fn fov_inverted_bounds() {
+    FovController::new(1., 1.5, 0.2);
+}
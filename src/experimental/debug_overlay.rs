@@ -0,0 +1,120 @@
+//! Immediate mode debug text, drawn over everything at the end of the frame.
+//!
+//! ```no_run
+//! use macroquad::experimental::debug_overlay::*;
+//! use macroquad::prelude::*;
+//!
+//! #[macroquad::main("Debug overlay")]
+//! async fn main() {
+//!     loop {
+//!         clear_background(WHITE);
+//!         debug_text("hello");
+//!         debug_value("fps", get_fps());
+//!         debug_value("mouse", mouse_position());
+//!         next_frame().await;
+//!     }
+//! }
+//! ```
+
+use crate::{
+    color::{Color, WHITE},
+    get_context,
+    shapes::draw_rectangle,
+    text::{draw_text, measure_text},
+};
+
+const FONT_SIZE: f32 = 20.;
+const PADDING: f32 = 4.;
+
+pub(crate) struct DebugOverlay {
+    lines: Vec<String>,
+    enabled: bool,
+}
+
+impl DebugOverlay {
+    pub(crate) fn new() -> DebugOverlay {
+        DebugOverlay {
+            lines: vec![],
+            enabled: true,
+        }
+    }
+}
+
+/// Add a line to this frame's debug overlay
+pub fn debug_text(line: &str) {
+    let overlay = &mut get_context().debug_overlay;
+
+    if overlay.enabled {
+        overlay.lines.push(line.to_string());
+    }
+}
+
+/// Add a "label: value" line to this frame's debug overlay
+pub fn debug_value<T: std::fmt::Debug>(label: &str, value: T) {
+    let overlay = &mut get_context().debug_overlay;
+
+    if overlay.enabled {
+        overlay.lines.push(format!("{}: {:?}", label, value));
+    }
+}
+
+/// Show or hide the debug overlay.
+/// While hidden, `debug_text` and `debug_value` do nothing.
+pub fn set_debug_overlay_enabled(enabled: bool) {
+    get_context().debug_overlay.enabled = enabled;
+}
+
+pub fn is_debug_overlay_enabled() -> bool {
+    get_context().debug_overlay.enabled
+}
+
+// Called once at the end of the frame, draws and clears accumulated lines.
+pub(crate) fn draw() {
+    let lines = std::mem::take(&mut get_context().debug_overlay.lines);
+    if lines.is_empty() {
+        return;
+    }
+
+    // whatever camera and material the frame ended with, draw on top of the screen.
+    // Previous draw calls are already flushed, so only the state needs to be reset,
+    // and restored afterwards as camera and material persist to the next frame.
+    let context = get_context();
+    let render_pass = context.gl.get_active_render_pass();
+    let viewport = context.gl.get_active_viewport();
+    let depth_test = context.gl.is_depth_test_enabled();
+    let pipeline = context.gl.get_active_pipeline();
+
+    context.gl.reset();
+    context.gl.render_pass(None);
+    context.gl.viewport(None);
+    context.gl.depth_test(false);
+    context.gl.pipeline(None);
+
+    let width = lines
+        .iter()
+        .map(|line| measure_text(line, None, FONT_SIZE as u16, 1.).width)
+        .fold(0., f32::max);
+    draw_rectangle(
+        0.,
+        0.,
+        width + PADDING * 2.,
+        lines.len() as f32 * FONT_SIZE + PADDING * 2.,
+        Color::new(0., 0., 0., 0.6),
+    );
+
+    for (n, line) in lines.iter().enumerate() {
+        draw_text(
+            line,
+            PADDING,
+            PADDING + (n + 1) as f32 * FONT_SIZE - FONT_SIZE / 4.,
+            FONT_SIZE,
+            WHITE,
+        );
+    }
+
+    let context = get_context();
+    context.gl.render_pass(render_pass);
+    context.gl.viewport(viewport);
+    context.gl.depth_test(depth_test);
+    context.gl.pipeline(pipeline);
+}
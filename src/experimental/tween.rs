@@ -0,0 +1,227 @@
+//! Easing functions and values interpolated over time.
+//!
+//! ```no_run
+//! use macroquad::experimental::tween::{ease, Tween};
+//! use macroquad::prelude::*;
+//!
+//! #[macroquad::main("Tween")]
+//! async fn main() {
+//!     let mut x = Tween::new(0., 300., 2., ease::bounce_out);
+//!     loop {
+//!         clear_background(WHITE);
+//!         draw_circle(x.update(), 100., 20., RED);
+//!         next_frame().await;
+//!     }
+//! }
+//! ```
+
+use crate::time::get_frame_time;
+
+use std::ops::{Add, Mul, Sub};
+
+/// Easing curves, mapping progress in [0, 1] to eased progress.
+/// All of them start at 0 and end at 1, elastic and back may overshoot in between.
+pub mod ease {
+    use std::f32::consts::PI;
+
+    pub fn linear(t: f32) -> f32 {
+        t
+    }
+
+    pub fn quad_in(t: f32) -> f32 {
+        t * t
+    }
+
+    pub fn quad_out(t: f32) -> f32 {
+        1. - (1. - t) * (1. - t)
+    }
+
+    pub fn quad_in_out(t: f32) -> f32 {
+        if t < 0.5 {
+            2. * t * t
+        } else {
+            1. - (-2. * t + 2.).powi(2) / 2.
+        }
+    }
+
+    pub fn cubic_in(t: f32) -> f32 {
+        t * t * t
+    }
+
+    pub fn cubic_out(t: f32) -> f32 {
+        1. - (1. - t).powi(3)
+    }
+
+    pub fn cubic_in_out(t: f32) -> f32 {
+        if t < 0.5 {
+            4. * t * t * t
+        } else {
+            1. - (-2. * t + 2.).powi(3) / 2.
+        }
+    }
+
+    pub fn sine_in_out(t: f32) -> f32 {
+        -((PI * t).cos() - 1.) / 2.
+    }
+
+    pub fn back_out(t: f32) -> f32 {
+        const C1: f32 = 1.70158;
+        const C3: f32 = C1 + 1.;
+
+        1. + C3 * (t - 1.).powi(3) + C1 * (t - 1.).powi(2)
+    }
+
+    pub fn elastic_in(t: f32) -> f32 {
+        if t <= 0. {
+            return 0.;
+        }
+        if t >= 1. {
+            return 1.;
+        }
+        -(2.0f32).powf(10. * t - 10.) * ((t * 10. - 10.75) * (2. * PI / 3.)).sin()
+    }
+
+    pub fn elastic_out(t: f32) -> f32 {
+        if t <= 0. {
+            return 0.;
+        }
+        if t >= 1. {
+            return 1.;
+        }
+        (2.0f32).powf(-10. * t) * ((t * 10. - 0.75) * (2. * PI / 3.)).sin() + 1.
+    }
+
+    pub fn bounce_in(t: f32) -> f32 {
+        1. - bounce_out(1. - t)
+    }
+
+    pub fn bounce_out(t: f32) -> f32 {
+        const N1: f32 = 7.5625;
+        const D1: f32 = 2.75;
+
+        if t < 1. / D1 {
+            N1 * t * t
+        } else if t < 2. / D1 {
+            let t = t - 1.5 / D1;
+            N1 * t * t + 0.75
+        } else if t < 2.5 / D1 {
+            let t = t - 2.25 / D1;
+            N1 * t * t + 0.9375
+        } else {
+            let t = t - 2.625 / D1;
+            N1 * t * t + 0.984375
+        }
+    }
+
+    pub fn bounce_in_out(t: f32) -> f32 {
+        if t < 0.5 {
+            (1. - bounce_out(1. - 2. * t)) / 2.
+        } else {
+            (1. + bounce_out(2. * t - 1.)) / 2.
+        }
+    }
+}
+
+/// Value going from `from` to `to` over `duration` seconds, following `ease` curve.
+///
+/// Works with anything that can be linearly interpolated: f32, Vec2, Vec3, Vec4.
+/// Colors may be tweened with `Color::to_vec` and `Color::from_vec`.
+#[derive(Clone, Copy, Debug)]
+pub struct Tween<T>
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + Mul<f32, Output = T>,
+{
+    pub from: T,
+    pub to: T,
+    /// Duration in seconds
+    pub duration: f32,
+    pub ease: fn(f32) -> f32,
+    elapsed: f32,
+}
+
+impl<T> Tween<T>
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + Mul<f32, Output = T>,
+{
+    pub fn new(from: T, to: T, duration: f32, ease: fn(f32) -> f32) -> Tween<T> {
+        Tween {
+            from,
+            to,
+            duration,
+            ease,
+            elapsed: 0.,
+        }
+    }
+
+    /// Advance the tween by the last frame time and return the current value.
+    pub fn update(&mut self) -> T {
+        self.advance(get_frame_time())
+    }
+
+    /// Advance the tween by `dt` seconds and return the current value.
+    pub fn advance(&mut self, dt: f32) -> T {
+        self.elapsed = (self.elapsed + dt).min(self.duration);
+
+        self.value()
+    }
+
+    pub fn value(&self) -> T {
+        self.from + (self.to - self.from) * (self.ease)(self.progress())
+    }
+
+    /// Progress in [0, 1], before easing
+    pub fn progress(&self) -> f32 {
+        if self.duration > 0. {
+            self.elapsed / self.duration
+        } else {
+            1.
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.elapsed >= self.duration
+    }
+
+    /// Start over from `from`
+    pub fn reset(&mut self) {
+        self.elapsed = 0.;
+    }
+}
+
+#[test]
+fn tween_ease() {
+    let curves: &[fn(f32) -> f32] = &[
+        ease::linear,
+        ease::quad_in,
+        ease::quad_out,
+        ease::quad_in_out,
+        ease::cubic_in,
+        ease::cubic_out,
+        ease::cubic_in_out,
+        ease::sine_in_out,
+        ease::back_out,
+        ease::elastic_in,
+        ease::elastic_out,
+        ease::bounce_in,
+        ease::bounce_out,
+        ease::bounce_in_out,
+    ];
+    for curve in curves {
+        assert!(curve(0.).abs() < 0.00001);
+        assert!((curve(1.) - 1.).abs() < 0.00001);
+    }
+
+    let mut tween = Tween::new(10., 20., 2., ease::linear);
+    assert_eq!(tween.advance(1.), 15.);
+    assert!(!tween.is_finished());
+    assert_eq!(tween.advance(5.), 20.);
+    assert_eq!(tween.progress(), 1.);
+    assert!(tween.is_finished());
+    tween.reset();
+    assert_eq!(tween.value(), 10.);
+
+    let mut instant = Tween::new(10., 20., 0., ease::quad_in);
+    assert_eq!(instant.value(), 20.);
+    assert_eq!(instant.advance(1.), 20.);
+    assert!(instant.is_finished());
+}
@@ -178,9 +178,11 @@ struct Context {
 
     gl: QuadGl,
     camera_matrix: Option<Mat4>,
+    background_color: Color,
 
     ui_context: UiContext,
     coroutines_context: experimental::coroutines::CoroutinesContext,
+    debug_overlay: experimental::debug_overlay::DebugOverlay,
     fonts_storage: text::FontsStorage,
 
     pc_assets_folder: Option<String>,
@@ -296,6 +298,7 @@ impl Context {
             input_events: Vec::new(),
 
             camera_matrix: None,
+            background_color: Self::DEFAULT_BG_COLOR,
             gl: QuadGl::new(ctx),
 
             ui_context: UiContext::new(ctx, screen_width, screen_height),
@@ -305,6 +308,7 @@ impl Context {
 
             audio_context: audio::AudioContext::new(),
             coroutines_context: experimental::coroutines::CoroutinesContext::new(),
+            debug_overlay: experimental::debug_overlay::DebugOverlay::new(),
 
             pc_assets_folder: None,
 
@@ -324,7 +328,7 @@ impl Context {
 
         self.ui_context.process_input();
 
-        let color = Self::DEFAULT_BG_COLOR;
+        let color = self.background_color;
 
         get_quad_context().clear(Some((color.r, color.g, color.b, color.a)), None, None);
         self.gl.reset();
@@ -335,6 +339,8 @@ impl Context {
 
         self.perform_render_passes();
 
+        crate::experimental::debug_overlay::draw();
+
         self.ui_context.draw(get_quad_context(), &mut self.gl);
         let screen_mat = self.pixel_perfect_projection_matrix();
         self.gl.draw(get_quad_context(), screen_mat);
@@ -19,6 +19,19 @@ impl Material {
         get_context().gl.set_uniform(self.pipeline, name, uniform);
     }
 
+    /// Set GPU uniform array for this material, starting from the first element.
+    /// "name" should be an array uniform from "uniforms" list, like "Lights[8]",
+    /// used without the brackets, "Lights".
+    /// Array elements should have the size of the uniform type.
+    ///
+    /// Elements are tightly packed, vec3 arrays should be set from `&[Vec3]` or `&[[f32; 3]]`,
+    /// without std140 padding to vec4.
+    pub fn set_uniform_array<T>(&self, name: &str, array: &[T]) {
+        get_context()
+            .gl
+            .set_uniform_array(self.pipeline, name, array);
+    }
+
     pub fn set_texture(&self, name: &str, texture: Texture2D) {
         get_context().gl.set_texture(self.pipeline, name, texture);
     }
@@ -39,6 +52,9 @@ pub struct MaterialParams {
     pub pipeline_params: PipelineParams,
 
     /// List of custom uniforms used in this material
+    ///
+    /// Arrays are declared with the size after the name, like "Lights[8]",
+    /// and set with `Material::set_uniform_array`.
     pub uniforms: Vec<(String, UniformType)>,
 
     /// List of textures used in this material
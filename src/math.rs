@@ -5,10 +5,14 @@
 pub use glam::*;
 
 mod circle;
+mod plane;
 mod rect;
+mod transform;
 
 pub use circle::Circle;
+pub use plane::Plane;
 pub use rect::{Rect, RectOffset};
+pub use transform::Transform;
 
 /// Converts 2d polar coordinates to 2d cartesian coordinates.
 pub fn polar_to_cartesian(rho: f32, theta: f32) -> Vec2 {
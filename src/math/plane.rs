@@ -0,0 +1,77 @@
+use glam::*;
+
+/// A 3D plane, all the points where `normal.dot(point) + d == 0`.
+/// Points on the side `normal` points to have positive distance.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Plane {
+    pub normal: Vec3,
+    pub d: f32,
+}
+
+impl Plane {
+    pub fn new(normal: Vec3, d: f32) -> Plane {
+        Plane { normal, d }
+    }
+
+    /// Plane going through `point` and facing `normal`.
+    pub fn from_point_normal(point: Vec3, normal: Vec3) -> Plane {
+        let normal = normal.normalize();
+
+        Plane {
+            normal,
+            d: -normal.dot(point),
+        }
+    }
+
+    /// Plane scaled to have a unit length normal, so `distance` is in world units.
+    pub fn normalize(&self) -> Plane {
+        let length = self.normal.length();
+
+        Plane {
+            normal: self.normal / length,
+            d: self.d / length,
+        }
+    }
+
+    /// Signed distance from the plane to `point`, positive in front of the plane.
+    /// Real distance only for a normalized plane.
+    pub fn distance(&self, point: Vec3) -> f32 {
+        self.normal.dot(point) + self.d
+    }
+
+    /// Extracts the six clip planes, facing inwards, from a view-projection matrix:
+    /// left, right, bottom, top, near, far.
+    /// A point is inside the view when its distance to all of them is positive.
+    ///
+    /// Expects OpenGL clip space, with z in -1..1, like all the `Camera` matrices.
+    pub fn frustum(matrix: Mat4) -> [Plane; 6] {
+        let (x, y, z, w) = (matrix.row(0), matrix.row(1), matrix.row(2), matrix.row(3));
+        let plane = |row: Vec4| Plane::new(row.truncate(), row.w).normalize();
+
+        [
+            plane(w + x),
+            plane(w - x),
+            plane(w + y),
+            plane(w - y),
+            plane(w + z),
+            plane(w - z),
+        ]
+    }
+}
+
+#[test]
+fn frustum_planes() {
+    let matrix = Mat4::perspective_rh_gl(1., 1.5, 0.1, 100.)
+        * Mat4::look_at_rh(vec3(0., 0., 10.), Vec3::ZERO, Vec3::Y);
+    let planes = Plane::frustum(matrix);
+
+    for point in &[Vec3::ZERO, vec3(1., -1., 5.), vec3(0., 0., -80.)] {
+        assert!(planes.iter().all(|plane| plane.distance(*point) > 0.));
+    }
+    // behind the camera, out to the side and past the far plane
+    for point in &[vec3(0., 0., 11.), vec3(50., 0., 0.), vec3(0., 0., -95.)] {
+        assert!(planes.iter().any(|plane| plane.distance(*point) < 0.));
+    }
+    // near plane is 0.1 in front of the camera
+    assert!((planes[4].distance(vec3(0., 0., 9.)) - 0.9).abs() < 0.0001);
+}
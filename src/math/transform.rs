@@ -0,0 +1,77 @@
+use glam::*;
+
+/// Translation, rotation and scale of an object.
+/// A more convenient way to build a model matrix than multiplying matrices by hand.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Transform {
+    pub translation: Vec3,
+    pub rotation: Quat,
+    pub scale: Vec3,
+}
+
+impl Default for Transform {
+    fn default() -> Transform {
+        Transform {
+            translation: Vec3::ZERO,
+            rotation: Quat::IDENTITY,
+            scale: Vec3::ONE,
+        }
+    }
+}
+
+impl Transform {
+    /// Creates a new transform from its translation, rotation and scale.
+    pub fn new(translation: Vec3, rotation: Quat, scale: Vec3) -> Transform {
+        Transform {
+            translation,
+            rotation,
+            scale,
+        }
+    }
+
+    /// Transform with the given translation, no rotation and unit scale.
+    pub fn from_translation(translation: Vec3) -> Transform {
+        Transform {
+            translation,
+            ..Default::default()
+        }
+    }
+
+    /// Matrix that scales, then rotates, then translates.
+    pub fn to_mat4(&self) -> Mat4 {
+        Mat4::from_scale_rotation_translation(self.scale, self.rotation, self.translation)
+    }
+
+    /// Decomposes a matrix into translation, rotation and scale.
+    /// Shear and perspective can not be represented and are lost.
+    pub fn from_mat4(matrix: Mat4) -> Transform {
+        let (scale, rotation, translation) = matrix.to_scale_rotation_translation();
+
+        Transform {
+            translation,
+            rotation,
+            scale,
+        }
+    }
+}
+
+impl From<Transform> for Mat4 {
+    fn from(transform: Transform) -> Mat4 {
+        transform.to_mat4()
+    }
+}
+
+#[test]
+fn transform_mat4_roundtrip() {
+    let transform = Transform::new(
+        vec3(1., -2., 3.),
+        Quat::from_euler(EulerRot::YXZ, 0.3, -1.2, 2.),
+        vec3(2., 0.5, 1.5),
+    );
+
+    let decomposed = Transform::from_mat4(transform.to_mat4());
+
+    assert!((decomposed.translation - transform.translation).length() < 0.0001);
+    assert!((decomposed.scale - transform.scale).length() < 0.0001);
+    assert!(decomposed.rotation.dot(transform.rotation).abs() > 0.9999);
+}
@@ -1,9 +1,12 @@
 //! 3D shapes and models, loading 3d models from files, drawing 3D primitives.
 
-use crate::{color::Color, get_context};
+use crate::{
+    color::{Color, BLUE, GREEN, RED},
+    get_context,
+};
 
 use crate::{quad_gl::DrawMode, texture::Texture2D};
-use glam::{vec2, vec3, Vec2, Vec3};
+use glam::{vec2, vec3, Mat4, Vec2, Vec3};
 
 #[derive(Clone, Debug, Copy)]
 pub struct Vertex {
@@ -28,6 +31,627 @@ pub struct Mesh {
     pub texture: Option<Texture2D>,
 }
 
+/// Ray intersection found by [Mesh::raycast]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RayHit {
+    /// Distance from the ray origin to the hit point
+    pub distance: f32,
+    /// Hit point in world space
+    pub point: Vec3,
+    /// Normal of the hit triangle in world space, following the triangle winding
+    pub normal: Vec3,
+}
+
+impl Mesh {
+    /// Tests a ray against every triangle of the mesh, transformed by `transform`,
+    /// and returns the nearest hit.
+    /// `direction` does not need to be normalized.
+    /// Triangles are tested from both sides.
+    pub fn raycast(&self, origin: Vec3, direction: Vec3, transform: Mat4) -> Option<RayHit> {
+        let direction = direction.normalize_or_zero();
+        if direction == Vec3::ZERO {
+            return None;
+        }
+
+        let mut nearest: Option<RayHit> = None;
+
+        for triangle in 0..self.indices.len() / 3 {
+            let (a, b, c) = match self.triangle(triangle) {
+                Some(triangle) => triangle,
+                None => continue,
+            };
+            let a = transform.transform_point3(a);
+            let b = transform.transform_point3(b);
+            let c = transform.transform_point3(c);
+
+            if let Some(distance) = ray_triangle_intersection(origin, direction, a, b, c) {
+                if nearest.map_or(f32::INFINITY, |hit| hit.distance) > distance {
+                    nearest = Some(RayHit {
+                        distance,
+                        point: origin + direction * distance,
+                        normal: (b - a).cross(c - a).normalize_or_zero(),
+                    });
+                }
+            }
+        }
+
+        nearest
+    }
+
+    /// Height of the mesh surface, transformed by `transform`, at world (x, z).
+    /// Casts a ray straight down from above the mesh and returns the y of the highest hit,
+    /// None if there is no surface under (x, z).
+    ///
+    /// Useful for walking on terrain meshes.
+    pub fn height_at(&self, x: f32, z: f32, transform: Mat4) -> Option<f32> {
+        let top = self
+            .vertices
+            .iter()
+            .map(|vertex| transform.transform_point3(vertex.position).y)
+            .fold(f32::NEG_INFINITY, f32::max);
+        if top == f32::NEG_INFINITY {
+            return None;
+        }
+
+        self.raycast(vec3(x, top + 1., z), -Vec3::Y, transform)
+            .map(|hit| hit.point.y)
+    }
+
+    /// Applies `transform` to the vertex positions, so the mesh may be drawn
+    /// without pushing a model matrix.
+    /// Useful for static meshes placed once, for example before merging them together.
+    pub fn bake_transform(&mut self, transform: Mat4) {
+        for vertex in &mut self.vertices {
+            vertex.position = transform.transform_point3(vertex.position);
+        }
+    }
+
+    /// Merges vertices with equal uv and color and positions closer than `epsilon`,
+    /// rebuilding the index buffer. Shrinks meshes built from unshared triangles,
+    /// like ones generated face by face or loaded from OBJ.
+    ///
+    /// Positions are snapped to a grid of `epsilon` sized cells, so two close
+    /// vertices on different sides of a cell boundary are kept apart.
+    ///
+    /// Out of range indices are left as they are, they stay out of range
+    /// as the vertex count only goes down. See [Mesh::validate].
+    pub fn weld_vertices(&mut self, epsilon: f32) {
+        use std::collections::HashMap;
+
+        let key = |vertex: &Vertex| {
+            let position = if epsilon > 0. {
+                let cell = (vertex.position / epsilon).round();
+                [cell.x as i64, cell.y as i64, cell.z as i64]
+            } else {
+                [
+                    vertex.position.x.to_bits() as i64,
+                    vertex.position.y.to_bits() as i64,
+                    vertex.position.z.to_bits() as i64,
+                ]
+            };
+            let uv = [vertex.uv.x.to_bits(), vertex.uv.y.to_bits()];
+            let Color { r, g, b, a } = vertex.color;
+            let color = [r.to_bits(), g.to_bits(), b.to_bits(), a.to_bits()];
+            (position, uv, color)
+        };
+
+        let mut welded = HashMap::new();
+        let mut vertices = vec![];
+        let remap: Vec<u16> = self
+            .vertices
+            .iter()
+            .map(|vertex| {
+                *welded.entry(key(vertex)).or_insert_with(|| {
+                    vertices.push(*vertex);
+                    (vertices.len() - 1) as u16
+                })
+            })
+            .collect();
+
+        for index in &mut self.indices {
+            if let Some(welded) = remap.get(*index as usize) {
+                *index = *welded;
+            }
+        }
+        self.vertices = vertices;
+    }
+
+    /// Wavefront OBJ text of the mesh, for inspecting generated meshes in external tools.
+    ///
+    /// Vertex colors are written after the position, an OBJ extension most tools understand.
+    /// UVs are flipped vertically, OBJ texture coordinates go bottom to top.
+    /// Texture is not exported.
+    pub fn to_obj(&self) -> String {
+        use std::fmt::Write;
+
+        let mut obj = String::new();
+
+        for vertex in &self.vertices {
+            let Vertex {
+                position,
+                color,
+                uv,
+            } = vertex;
+            writeln!(
+                obj,
+                "v {} {} {} {} {} {}",
+                position.x, position.y, position.z, color.r, color.g, color.b
+            )
+            .unwrap();
+            writeln!(obj, "vt {} {}", uv.x, 1. - uv.y).unwrap();
+        }
+        for triangle in self.indices.chunks_exact(3) {
+            // OBJ indices start from 1
+            let (a, b, c) = (
+                triangle[0] as u32 + 1,
+                triangle[1] as u32 + 1,
+                triangle[2] as u32 + 1,
+            );
+            writeln!(obj, "f {}/{} {}/{} {}/{}", a, a, b, b, c, c).unwrap();
+        }
+
+        obj
+    }
+
+    /// Axis aligned bounding box of all the mesh vertices, as (min, max).
+    /// None for a mesh without vertices.
+    pub fn bounding_box(&self) -> Option<(Vec3, Vec3)> {
+        let first = self.vertices.first()?.position;
+
+        Some(
+            self.vertices
+                .iter()
+                .fold((first, first), |(min, max), vertex| {
+                    (min.min(vertex.position), max.max(vertex.position))
+                }),
+        )
+    }
+
+    /// Vertices of the convex hull of all the mesh vertex positions,
+    /// a cheap collision shape for physics engines.
+    ///
+    /// Flat meshes give the outline of the polygon they lie in,
+    /// in no particular order.
+    pub fn convex_hull(&self) -> Vec<Vec3> {
+        let points: Vec<Vec3> = self.vertices.iter().map(|vertex| vertex.position).collect();
+
+        convex_hull(&points)
+    }
+
+    /// Builds a bounding volume hierarchy over the mesh triangles,
+    /// to speed up repeated raycasts against big meshes with [MeshBvh::raycast].
+    /// The hierarchy is built from the current vertex positions
+    /// and should be rebuilt after the mesh is modified.
+    pub fn build_bvh(&self) -> MeshBvh {
+        MeshBvh::new(self)
+    }
+
+    fn triangle(&self, triangle: usize) -> Option<(Vec3, Vec3, Vec3)> {
+        let indices = self.indices.get(triangle * 3..triangle * 3 + 3)?;
+
+        Some((
+            self.vertices.get(indices[0] as usize)?.position,
+            self.vertices.get(indices[1] as usize)?.position,
+            self.vertices.get(indices[2] as usize)?.position,
+        ))
+    }
+}
+
+/// Problem found by [Mesh::validate]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ValidationIssue {
+    /// Index count is not a multiple of 3, the last indices are ignored
+    IncompleteTriangle { indices: usize },
+    /// Index points past the end of `vertices`
+    IndexOutOfRange { index: usize, vertex: u16 },
+    /// Vertex position or uv is NaN or infinite
+    NonFiniteVertex { vertex: usize },
+    /// UV outside of [0, 1], fine for repeating textures
+    UvOutOfRange { vertex: usize },
+    /// Triangle with zero area, never drawn
+    DegenerateTriangle { triangle: usize },
+}
+
+impl Mesh {
+    /// Checks the mesh data for common problems, without modifying it.
+    ///
+    /// Useful in asset pipelines and tools to flag broken meshes.
+    pub fn validate(&self) -> Vec<ValidationIssue> {
+        let mut issues = vec![];
+
+        if !self.indices.chunks_exact(3).remainder().is_empty() {
+            issues.push(ValidationIssue::IncompleteTriangle {
+                indices: self.indices.len(),
+            });
+        }
+        for (index, vertex) in self.indices.iter().enumerate() {
+            if *vertex as usize >= self.vertices.len() {
+                issues.push(ValidationIssue::IndexOutOfRange {
+                    index,
+                    vertex: *vertex,
+                });
+            }
+        }
+        for (n, vertex) in self.vertices.iter().enumerate() {
+            if !vertex.position.is_finite() || !vertex.uv.is_finite() {
+                issues.push(ValidationIssue::NonFiniteVertex { vertex: n });
+            } else if vertex.uv.min_element() < 0. || vertex.uv.max_element() > 1. {
+                issues.push(ValidationIssue::UvOutOfRange { vertex: n });
+            }
+        }
+        for triangle in 0..self.indices.len() / 3 {
+            if let Some((a, b, c)) = self.triangle(triangle) {
+                // relative to the edges, so tiny but well shaped triangles are fine
+                let (ab, ac) = (b - a, c - a);
+                if ab.cross(ac).length() <= f32::EPSILON * ab.length() * ac.length() {
+                    issues.push(ValidationIssue::DegenerateTriangle { triangle });
+                }
+            }
+        }
+
+        issues
+    }
+}
+
+/// Bounding volume hierarchy over the triangles of a [Mesh], made with [Mesh::build_bvh]
+#[derive(Clone, Debug)]
+pub struct MeshBvh {
+    nodes: Vec<BvhNode>,
+    triangles: Vec<usize>,
+}
+
+#[derive(Clone, Debug)]
+struct BvhNode {
+    min: Vec3,
+    max: Vec3,
+    // for leaves - first triangle in `MeshBvh::triangles`,
+    // for inner nodes - left child, the right one goes right after it
+    first: usize,
+    // 0 for inner nodes
+    count: usize,
+}
+
+impl MeshBvh {
+    const MAX_LEAF_TRIANGLES: usize = 4;
+
+    fn new(mesh: &Mesh) -> MeshBvh {
+        let triangles_count = mesh.indices.len() / 3;
+        let triangles: Vec<usize> = (0..triangles_count)
+            .filter(|triangle| mesh.triangle(*triangle).is_some())
+            .collect();
+        let centroids: Vec<Vec3> = (0..triangles_count)
+            .map(|triangle| {
+                mesh.triangle(triangle)
+                    .map_or(Vec3::ZERO, |(a, b, c)| (a + b + c) / 3.)
+            })
+            .collect();
+
+        if triangles.is_empty() {
+            return MeshBvh {
+                nodes: vec![],
+                triangles,
+            };
+        }
+
+        let mut bvh = MeshBvh {
+            nodes: vec![BvhNode {
+                min: Vec3::ZERO,
+                max: Vec3::ZERO,
+                first: 0,
+                count: triangles.len(),
+            }],
+            triangles,
+        };
+        bvh.subdivide(mesh, &centroids, 0);
+
+        bvh
+    }
+
+    fn subdivide(&mut self, mesh: &Mesh, centroids: &[Vec3], node: usize) {
+        let first = self.nodes[node].first;
+        let count = self.nodes[node].count;
+        let triangles = &mut self.triangles[first..first + count];
+
+        let mut min = Vec3::splat(f32::INFINITY);
+        let mut max = Vec3::splat(-f32::INFINITY);
+        let mut centroids_min = Vec3::splat(f32::INFINITY);
+        let mut centroids_max = Vec3::splat(-f32::INFINITY);
+        for triangle in triangles.iter() {
+            let (a, b, c) = mesh.triangle(*triangle).unwrap();
+            min = min.min(a).min(b).min(c);
+            max = max.max(a).max(b).max(c);
+            centroids_min = centroids_min.min(centroids[*triangle]);
+            centroids_max = centroids_max.max(centroids[*triangle]);
+        }
+        self.nodes[node].min = min;
+        self.nodes[node].max = max;
+
+        if count <= Self::MAX_LEAF_TRIANGLES {
+            return;
+        }
+
+        // split by the median centroid along the longest axis
+        let extent = centroids_max - centroids_min;
+        let axis = if extent.x >= extent.y && extent.x >= extent.z {
+            0
+        } else if extent.y >= extent.z {
+            1
+        } else {
+            2
+        };
+        let middle = count / 2;
+        triangles.select_nth_unstable_by(middle, |a, b| {
+            centroids[*a][axis]
+                .partial_cmp(&centroids[*b][axis])
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        let left = self.nodes.len();
+        self.nodes.push(BvhNode {
+            min: Vec3::ZERO,
+            max: Vec3::ZERO,
+            first,
+            count: middle,
+        });
+        self.nodes.push(BvhNode {
+            min: Vec3::ZERO,
+            max: Vec3::ZERO,
+            first: first + middle,
+            count: count - middle,
+        });
+        self.nodes[node].first = left;
+        self.nodes[node].count = 0;
+
+        self.subdivide(mesh, centroids, left);
+        self.subdivide(mesh, centroids, left + 1);
+    }
+
+    /// Same as [Mesh::raycast], but skips triangles whose bounds are not crossed by the ray.
+    /// `mesh` should be the mesh this hierarchy was built from.
+    pub fn raycast(
+        &self,
+        mesh: &Mesh,
+        origin: Vec3,
+        direction: Vec3,
+        transform: Mat4,
+    ) -> Option<RayHit> {
+        let direction = direction.normalize_or_zero();
+        if direction == Vec3::ZERO || self.nodes.is_empty() {
+            return None;
+        }
+
+        // The hierarchy is built in mesh space, so the ray is moved there instead.
+        // Direction is not normalized after the transform, so the distance along the ray
+        // is the same in both spaces.
+        let inverse = transform.inverse();
+        let local_origin = inverse.transform_point3(origin);
+        let local_direction = inverse.transform_vector3(direction);
+        let inverse_direction = local_direction.recip();
+
+        let mut nearest: Option<(f32, usize)> = None;
+        let mut stack = vec![0];
+        while let Some(node) = stack.pop() {
+            let node = &self.nodes[node];
+            let max_distance = nearest.map_or(f32::INFINITY, |(distance, _)| distance);
+
+            if !ray_aabb_intersection(
+                local_origin,
+                inverse_direction,
+                node.min,
+                node.max,
+                max_distance,
+            ) {
+                continue;
+            }
+
+            if node.count == 0 {
+                stack.push(node.first);
+                stack.push(node.first + 1);
+                continue;
+            }
+
+            for triangle in &self.triangles[node.first..node.first + node.count] {
+                let (a, b, c) = match mesh.triangle(*triangle) {
+                    Some(triangle) => triangle,
+                    None => continue,
+                };
+                if let Some(distance) =
+                    ray_triangle_intersection(local_origin, local_direction, a, b, c)
+                {
+                    if nearest.map_or(f32::INFINITY, |(distance, _)| distance) > distance {
+                        nearest = Some((distance, *triangle));
+                    }
+                }
+            }
+        }
+
+        let (distance, triangle) = nearest?;
+        let (a, b, c) = mesh.triangle(triangle)?;
+        let a = transform.transform_point3(a);
+        let b = transform.transform_point3(b);
+        let c = transform.transform_point3(c);
+
+        Some(RayHit {
+            distance,
+            point: origin + direction * distance,
+            normal: (b - a).cross(c - a).normalize_or_zero(),
+        })
+    }
+}
+
+// Slab test, `inverse_direction` is 1 / ray direction
+fn ray_aabb_intersection(
+    origin: Vec3,
+    inverse_direction: Vec3,
+    min: Vec3,
+    max: Vec3,
+    max_distance: f32,
+) -> bool {
+    let t1 = (min - origin) * inverse_direction;
+    let t2 = (max - origin) * inverse_direction;
+    let t_enter = t1.min(t2).max_element().max(0.);
+    let t_exit = t1.max(t2).min_element();
+
+    t_enter <= t_exit && t_enter <= max_distance
+}
+
+// Möller–Trumbore ray-triangle intersection, returns distance along the ray
+fn ray_triangle_intersection(
+    origin: Vec3,
+    direction: Vec3,
+    a: Vec3,
+    b: Vec3,
+    c: Vec3,
+) -> Option<f32> {
+    let edge1 = b - a;
+    let edge2 = c - a;
+    let p = direction.cross(edge2);
+    let det = edge1.dot(p);
+    // relative to the sizes, the direction is not normalized for transformed rays
+    if det.abs() <= f32::EPSILON * edge1.length() * edge2.length() * direction.length() {
+        return None;
+    }
+    let inv_det = 1. / det;
+
+    let s = origin - a;
+    let u = s.dot(p) * inv_det;
+    if !(0.0..=1.0).contains(&u) {
+        return None;
+    }
+
+    let q = s.cross(edge1);
+    let v = direction.dot(q) * inv_det;
+    if v < 0. || u + v > 1. {
+        return None;
+    }
+
+    let t = edge2.dot(q) * inv_det;
+    if t < 0. {
+        return None;
+    }
+
+    Some(t)
+}
+
+// Incremental convex hull: starts from a tetrahedron and adds points one by one,
+// replacing the faces a point can see with a fan from the point to the horizon
+fn convex_hull(points: &[Vec3]) -> Vec<Vec3> {
+    let (min, max) = match points.first() {
+        Some(first) => points.iter().fold((*first, *first), |(min, max), point| {
+            (min.min(*point), max.max(*point))
+        }),
+        None => return vec![],
+    };
+    let epsilon = (max - min).max_element() * 1e-5;
+
+    let farthest = |distance: &dyn Fn(Vec3) -> f32| {
+        (0..points.len()).map(|i| (i, distance(points[i]))).fold(
+            (0, f32::NEG_INFINITY),
+            |best, candidate| {
+                if candidate.1 > best.1 {
+                    candidate
+                } else {
+                    best
+                }
+            },
+        )
+    };
+
+    let a = farthest(&|point| -point.x).0;
+    let (b, distance) = farthest(&|point| point.distance(points[a]));
+    if distance <= epsilon {
+        return vec![points[a]];
+    }
+    let line = (points[b] - points[a]).normalize();
+    let (c, distance) = farthest(&|point| line.cross(point - points[a]).length());
+    if distance <= epsilon {
+        return vec![points[a], points[b]];
+    }
+    let normal = line.cross(points[c] - points[a]).normalize();
+    let (d, distance) = farthest(&|point| normal.dot(point - points[a]).abs());
+    if distance <= epsilon {
+        return convex_hull_planar(points, points[a], line, normal);
+    }
+
+    let face_normal = |face: [usize; 3]| {
+        (points[face[1]] - points[face[0]]).cross(points[face[2]] - points[face[0]])
+    };
+    let sees = |face: [usize; 3], point: Vec3| {
+        face_normal(face).normalize().dot(point - points[face[0]]) > epsilon
+    };
+
+    // tetrahedron with all faces pointing outwards
+    let mut faces: Vec<[usize; 3]> = vec![[a, b, c], [a, c, d], [a, d, b], [b, d, c]];
+    if sees([a, b, c], points[d]) {
+        for face in &mut faces {
+            face.swap(1, 2);
+        }
+    }
+
+    for (n, point) in points.iter().enumerate() {
+        if n == a || n == b || n == c || n == d {
+            continue;
+        }
+        let (visible, hidden): (Vec<[usize; 3]>, Vec<[usize; 3]>) =
+            faces.iter().partition(|face| sees(**face, *point));
+        if visible.is_empty() {
+            continue;
+        }
+
+        // edges of visible faces not shared with another visible face
+        let edges: Vec<(usize, usize)> = visible
+            .iter()
+            .flat_map(|face| vec![(face[0], face[1]), (face[1], face[2]), (face[2], face[0])])
+            .collect();
+        faces = hidden;
+        for &(from, to) in &edges {
+            if !edges.contains(&(to, from)) {
+                faces.push([from, to, n]);
+            }
+        }
+    }
+
+    let mut hull: Vec<usize> = faces.iter().flat_map(|face| face.to_vec()).collect();
+    hull.sort_unstable();
+    hull.dedup();
+
+    hull.into_iter().map(|i| points[i]).collect()
+}
+
+// 2D monotone chain hull of points lying in a plane
+fn convex_hull_planar(points: &[Vec3], origin: Vec3, u: Vec3, normal: Vec3) -> Vec<Vec3> {
+    let v = normal.cross(u);
+    let mut projected: Vec<(Vec2, Vec3)> = points
+        .iter()
+        .map(|point| (vec2(u.dot(*point - origin), v.dot(*point - origin)), *point))
+        .collect();
+    projected.sort_by(|a, b| {
+        (a.0.x, a.0.y)
+            .partial_cmp(&(b.0.x, b.0.y))
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    let cross = |o: Vec2, a: Vec2, b: Vec2| (a - o).perp_dot(b - o);
+    let mut hull: Vec<(Vec2, Vec3)> = vec![];
+    for pass in 0..2 {
+        let start = hull.len();
+        for point in &projected {
+            while hull.len() >= start + 2
+                && cross(hull[hull.len() - 2].0, hull[hull.len() - 1].0, point.0) <= 0.
+            {
+                hull.pop();
+            }
+            hull.push(*point);
+        }
+        hull.pop();
+        if pass == 0 {
+            projected.reverse();
+        }
+    }
+
+    hull.into_iter().map(|(_, point)| point).collect()
+}
+
 pub fn draw_mesh(mesh: &Mesh) {
     let context = get_context();
 
@@ -36,6 +660,26 @@ pub fn draw_mesh(mesh: &Mesh) {
     context.gl.geometry(&mesh.vertices[..], &mesh.indices[..]);
 }
 
+/// Draws `mesh` once for each transform.
+/// Vertices are transformed on the CPU, so the copies share draw calls
+/// (up to the draw call buffer capacity, see `gl_set_drawcall_buffer_capacity`)
+/// instead of one draw call per copy as with `push_model_matrix` and `draw_mesh`.
+/// Best for many copies of small meshes.
+pub fn draw_mesh_many(mesh: &Mesh, transforms: &[Mat4]) {
+    let context = get_context();
+
+    context.gl.texture(mesh.texture);
+    context.gl.draw_mode(DrawMode::Triangles);
+
+    let mut vertices = mesh.vertices.clone();
+    for transform in transforms {
+        for (vertex, source) in vertices.iter_mut().zip(&mesh.vertices) {
+            vertex.position = transform.transform_point3(source.position);
+        }
+        context.gl.geometry(&vertices[..], &mesh.indices[..]);
+    }
+}
+
 fn draw_quad(vertices: [(Vec3, Vec2, Color); 4]) {
     let context = get_context();
     let indices = [0, 1, 2, 0, 2, 3];
@@ -100,6 +744,58 @@ pub fn draw_grid(slices: u32, spacing: f32, axes_color: Color, other_color: Colo
     }
 }
 
+/// Draw a translation gizmo at `position`: X, Y and Z arrows colored red, green and blue,
+/// each `scale` long.
+/// Gizmo is drawn on top of everything else, ignoring depth.
+/// Nothing is drawn for a `scale` of zero or less.
+pub fn draw_gizmo(position: Vec3, scale: f32) {
+    if scale <= 0. {
+        return;
+    }
+
+    let context = get_context();
+    let depth_test = context.gl.is_depth_test_enabled();
+    context.gl.depth_test(false);
+
+    for &(axis, color) in &[(Vec3::X, RED), (Vec3::Y, GREEN), (Vec3::Z, BLUE)] {
+        let tip = position + axis * scale;
+        let base = position + axis * scale * 0.8;
+
+        draw_line_3d(position, base, color);
+        draw_cone(base, tip, scale * 0.06, color);
+    }
+
+    get_context().gl.depth_test(depth_test);
+}
+
+fn draw_cone(base: Vec3, tip: Vec3, radius: f32, color: Color) {
+    const SEGMENTS: usize = 8;
+
+    let context = get_context();
+    let axis = (tip - base).normalize();
+    let side = axis.any_orthonormal_vector();
+    let up = axis.cross(side);
+    let color: [f32; 4] = color.into();
+
+    let mut vertices = Vec::with_capacity(SEGMENTS + 2);
+    let mut indices = Vec::with_capacity(SEGMENTS * 6);
+    vertices.push((tip.into(), [0., 0.], color));
+    vertices.push((base.into(), [0., 0.], color));
+    for i in 0..SEGMENTS {
+        let angle = i as f32 / SEGMENTS as f32 * std::f32::consts::PI * 2.;
+        let point = base + (side * angle.cos() + up * angle.sin()) * radius;
+        vertices.push((point.into(), [0., 0.], color));
+
+        let current = i as u16 + 2;
+        let next = (i + 1) as u16 % SEGMENTS as u16 + 2;
+        indices.extend_from_slice(&[0, current, next, 1, next, current]);
+    }
+
+    context.gl.texture(None);
+    context.gl.draw_mode(DrawMode::Triangles);
+    context.gl.geometry(&vertices, &indices);
+}
+
 pub fn draw_plane(center: Vec3, size: Vec2, texture: impl Into<Option<Texture2D>>, color: Color) {
     let v1 = (
         (center + vec3(-size.x, 0., -size.y)).into(),
@@ -466,3 +1162,351 @@ pub fn draw_sphere_ex(
         }
     }
 }
+
+#[cfg(test)]
+fn vertex(x: f32, y: f32, z: f32) -> Vertex {
+    Vertex {
+        position: vec3(x, y, z),
+        uv: vec2(0., 0.),
+        color: crate::color::WHITE,
+    }
+}
+
+/// Flat grid of `size` x `size` cells on the XZ plane, starting at the origin
+#[cfg(test)]
+fn grid_mesh(size: usize, spacing: f32) -> Mesh {
+    let mut vertices = vec![];
+    let mut indices = vec![];
+    for z in 0..=size {
+        for x in 0..=size {
+            vertices.push(vertex(x as f32 * spacing, 0., z as f32 * spacing));
+        }
+    }
+    let row = size as u16 + 1;
+    for z in 0..size {
+        for x in 0..size {
+            let i = (z * (size + 1) + x) as u16;
+            indices.extend_from_slice(&[i, i + 1, i + row + 1, i, i + row + 1, i + row]);
+        }
+    }
+
+    Mesh {
+        vertices,
+        indices,
+        texture: None,
+    }
+}
+
+/// 32x32 grid with bumps, for raycasts to hit at different heights
+#[cfg(test)]
+fn bumpy_grid_mesh() -> Mesh {
+    let mut mesh = grid_mesh(32, 1.);
+    for (n, vertex) in mesh.vertices.iter_mut().enumerate() {
+        let (x, z) = (n % 33, n / 33);
+        vertex.position.y = ((x * 7 + z * 3) % 5) as f32 * 0.3;
+    }
+    mesh
+}
+
+#[cfg(test)]
+fn bvh_test_rays(count: usize) -> impl Iterator<Item = (Vec3, Vec3)> {
+    (0..count).map(|i| {
+        (
+            vec3(i as f32 * 0.37 - 4., 10., i as f32 * 0.21 - 1.),
+            vec3((i % 7) as f32 * 0.1 - 0.3, -1., (i % 3) as f32 * 0.2 - 0.2),
+        )
+    })
+}
+
+#[test]
+fn mesh_raycast() {
+    // 2x2 square centered at the origin
+    let mut mesh = grid_mesh(1, 2.);
+    mesh.bake_transform(Mat4::from_translation(vec3(-1., 0., -1.)));
+    let transform = Mat4::from_translation(vec3(0., 1., 0.));
+
+    let hit = mesh
+        .raycast(vec3(0.5, 5., 0.5), vec3(0., -2., 0.), transform)
+        .unwrap();
+    assert!((hit.distance - 4.).abs() < 0.0001);
+    assert!((hit.point - vec3(0.5, 1., 0.5)).length() < 0.0001);
+    assert!((hit.normal.abs() - vec3(0., 1., 0.)).length() < 0.0001);
+
+    assert!(mesh
+        .raycast(vec3(2., 5., 0.), vec3(0., -1., 0.), transform)
+        .is_none());
+    assert!(mesh
+        .raycast(vec3(0., 5., 0.), vec3(0., 1., 0.), transform)
+        .is_none());
+
+    assert!((mesh.height_at(0.3, -0.7, transform).unwrap() - 1.).abs() < 0.0001);
+    assert!(mesh.height_at(3., 0., transform).is_none());
+
+    let mut baked = Mesh {
+        vertices: mesh.vertices.clone(),
+        indices: mesh.indices.clone(),
+        texture: None,
+    };
+    baked.bake_transform(transform);
+    assert_eq!(
+        baked.raycast(vec3(0.5, 5., 0.5), vec3(0., -2., 0.), Mat4::IDENTITY),
+        mesh.raycast(vec3(0.5, 5., 0.5), vec3(0., -2., 0.), transform)
+    );
+}
+
+#[test]
+fn mesh_bvh_raycast() {
+    let mesh = bumpy_grid_mesh();
+    let bvh = mesh.build_bvh();
+    let transform = Mat4::from_scale_rotation_translation(
+        vec3(0.5, 2., 0.5),
+        glam::Quat::from_rotation_y(0.3),
+        vec3(-3., 1., 2.),
+    );
+
+    let mut hits = 0;
+    for (origin, direction) in bvh_test_rays(50) {
+        let brute_force = mesh.raycast(origin, direction, transform);
+        let accelerated = bvh.raycast(&mesh, origin, direction, transform);
+        assert_eq!(brute_force.is_some(), accelerated.is_some());
+        if let (Some(a), Some(b)) = (brute_force, accelerated) {
+            assert!((a.distance - b.distance).abs() < 0.001);
+            assert!((a.point - b.point).length() < 0.001);
+            hits += 1;
+        }
+    }
+    assert!(hits > 25);
+
+    // tiny mesh under a big scale, mesh space direction is far from unit length
+    let mesh = grid_mesh(20, 0.001);
+    let bvh = mesh.build_bvh();
+    let transform = Mat4::from_scale(Vec3::splat(1000.));
+    let origin = vec3(5.5, 10., 7.3);
+    let direction = vec3(0., -1., 0.);
+
+    let brute_force = mesh.raycast(origin, direction, transform).unwrap();
+    let accelerated = bvh.raycast(&mesh, origin, direction, transform).unwrap();
+    assert!((brute_force.distance - 10.).abs() < 0.001);
+    assert!((accelerated.distance - 10.).abs() < 0.001);
+}
+
+#[test]
+fn mesh_convex_hull() {
+    let mut vertices = vec![];
+    for &x in &[-1., 1.] {
+        for &y in &[-1., 1.] {
+            for &z in &[-1., 1.] {
+                vertices.push(vertex(x, y, z));
+            }
+        }
+    }
+    // points inside and on the faces are not on the hull
+    vertices.push(vertex(0., 0., 0.));
+    vertices.push(vertex(0.5, -0.3, 0.2));
+    vertices.push(vertex(1., 0., 0.));
+    vertices.push(vertex(0., -1., 0.5));
+
+    let mesh = Mesh {
+        vertices,
+        indices: vec![],
+        texture: None,
+    };
+    let hull = mesh.convex_hull();
+    assert_eq!(hull.len(), 8);
+    assert!(hull.iter().all(|point| point.abs() == Vec3::ONE));
+
+    let square = Mesh {
+        vertices: vec![
+            vertex(0., 0., 0.),
+            vertex(1., 0., 0.),
+            vertex(1., 0., 1.),
+            vertex(0., 0., 1.),
+            vertex(0.5, 0., 0.5),
+        ],
+        indices: vec![],
+        texture: None,
+    };
+    assert_eq!(square.convex_hull().len(), 4);
+}
+
+#[test]
+fn mesh_weld_vertices() {
+    use crate::color::WHITE;
+
+    // cube with every triangle having its own vertices
+    let corners = [vec2(0., 0.), vec2(1., 0.), vec2(1., 1.), vec2(0., 1.)];
+    let faces = [
+        (Vec3::X, Vec3::Y, Vec3::Z),
+        (-Vec3::X, Vec3::Z, Vec3::Y),
+        (Vec3::Y, Vec3::Z, Vec3::X),
+        (-Vec3::Y, Vec3::X, Vec3::Z),
+        (Vec3::Z, Vec3::X, Vec3::Y),
+        (-Vec3::Z, Vec3::Y, Vec3::X),
+    ];
+    let mut vertices = vec![];
+    for &(normal, u, v) in &faces {
+        for &i in &[0, 1, 2, 0, 2, 3] {
+            let uv: Vec2 = corners[i];
+            vertices.push(Vertex {
+                // tiny noise, as left by exporters
+                position: normal
+                    + u * (uv.x * 2. - 1.)
+                    + v * (uv.y * 2. - 1.)
+                    + vertices.len() as f32 * 1e-6,
+                uv,
+                color: WHITE,
+            });
+        }
+    }
+    let indices = (0..vertices.len() as u16).collect();
+    let mut mesh = Mesh {
+        vertices,
+        indices,
+        texture: None,
+    };
+    let before: Vec<Vec3> = mesh
+        .indices
+        .iter()
+        .map(|&i| mesh.vertices[i as usize].position)
+        .collect();
+
+    mesh.weld_vertices(0.001);
+    assert_eq!(mesh.indices.len(), 36);
+    assert!(mesh.vertices.len() <= 24);
+    for (&i, position) in mesh.indices.iter().zip(before) {
+        assert!((mesh.vertices[i as usize].position - position).length() < 0.001);
+    }
+
+    let mut broken = Mesh {
+        vertices: mesh.vertices[0..3].to_vec(),
+        indices: vec![0, 1, 9999],
+        texture: None,
+    };
+    broken.weld_vertices(0.001);
+    assert_eq!(broken.indices, vec![0, 1, 9999]);
+}
+
+#[test]
+fn mesh_validate() {
+    let vertex = |x, y, u| Vertex {
+        uv: vec2(u, 0.),
+        ..vertex(x, y, 0.)
+    };
+    let clean = Mesh {
+        vertices: vec![vertex(0., 0., 0.), vertex(1., 0., 1.), vertex(0., 1., 0.5)],
+        indices: vec![0, 1, 2],
+        texture: None,
+    };
+    assert_eq!(clean.validate(), vec![]);
+
+    let mut tiny = Mesh {
+        vertices: clean.vertices.clone(),
+        indices: vec![0, 1, 2],
+        texture: None,
+    };
+    tiny.bake_transform(Mat4::from_scale(Vec3::splat(0.0001)));
+    assert_eq!(tiny.validate(), vec![]);
+
+    let broken = Mesh {
+        vertices: vec![
+            vertex(0., 0., 0.),
+            vertex(1., 0., 1.),
+            vertex(0., 1., 0.5),
+            vertex(f32::NAN, 0., 0.),
+            vertex(0., 0., 2.),
+            vertex(2., 0., 0.),
+        ],
+        // good triangle, degenerate triangle, triangle with a bad index and a leftover index
+        indices: vec![0, 1, 2, 0, 1, 5, 0, 1, 9, 0],
+        texture: None,
+    };
+    assert_eq!(
+        broken.validate(),
+        vec![
+            ValidationIssue::IncompleteTriangle { indices: 10 },
+            ValidationIssue::IndexOutOfRange {
+                index: 8,
+                vertex: 9
+            },
+            ValidationIssue::NonFiniteVertex { vertex: 3 },
+            ValidationIssue::UvOutOfRange { vertex: 4 },
+            ValidationIssue::DegenerateTriangle { triangle: 1 },
+        ]
+    );
+}
+
+/// `cargo test --release mesh_bvh_benchmark -- --ignored --nocapture`
+#[test]
+#[ignore]
+fn mesh_bvh_benchmark() {
+    use std::time::Instant;
+
+    let mesh = grid_mesh(150, 0.1);
+    let transform = Mat4::IDENTITY;
+    let rays: Vec<_> = bvh_test_rays(2000).collect();
+
+    let start = Instant::now();
+    let bvh = mesh.build_bvh();
+    let build = start.elapsed();
+
+    let start = Instant::now();
+    let brute_force_hits = rays
+        .iter()
+        .filter(|(origin, direction)| mesh.raycast(*origin, *direction, transform).is_some())
+        .count();
+    let brute_force = start.elapsed();
+
+    let start = Instant::now();
+    let accelerated_hits = rays
+        .iter()
+        .filter(|(origin, direction)| bvh.raycast(&mesh, *origin, *direction, transform).is_some())
+        .count();
+    let accelerated = start.elapsed();
+
+    println!(
+        "{} triangles, {} rays: brute force {:?}, bvh {:?} (+{:?} to build)",
+        mesh.indices.len() / 3,
+        rays.len(),
+        brute_force,
+        accelerated,
+        build
+    );
+    assert_eq!(brute_force_hits, accelerated_hits);
+    assert!(accelerated < brute_force);
+}
+
+#[test]
+fn mesh_to_obj() {
+    let mesh = Mesh {
+        vertices: vec![
+            Vertex {
+                uv: vec2(0.25, 0.),
+                ..vertex(0., 0., 0.)
+            },
+            Vertex {
+                uv: vec2(1., 0.75),
+                ..vertex(1., 0., 0.)
+            },
+            vertex(0., 1., 0.),
+        ],
+        // trailing incomplete triangle is skipped
+        indices: vec![0, 1, 2, 2, 1],
+        texture: None,
+    };
+    let obj = mesh.to_obj();
+    let lines: Vec<&str> = obj.lines().collect();
+
+    assert_eq!(
+        lines.iter().filter(|line| line.starts_with("v ")).count(),
+        3
+    );
+    assert_eq!(lines[1], "vt 0.25 1");
+    assert_eq!(lines[3], "vt 1 0.25");
+    assert_eq!(
+        lines
+            .iter()
+            .filter(|line| line.starts_with("f "))
+            .collect::<Vec<_>>(),
+        vec![&"f 1/1 2/2 3/3"]
+    );
+}
@@ -307,9 +307,21 @@ impl GlState {
 struct Uniform {
     name: String,
     uniform_type: UniformType,
+    array_count: usize,
     byte_offset: usize,
 }
 
+/// Splits uniform name like "Lights[4]" into the GLSL name and array size,
+/// names without brackets are single values.
+fn uniform_name_and_array_count(name: &str) -> (&str, usize) {
+    if let (Some(open), true) = (name.find('['), name.ends_with(']')) {
+        if let Ok(count) = name[open + 1..name.len() - 1].trim().parse() {
+            return (&name[..open], count);
+        }
+    }
+    (name, 1)
+}
+
 #[derive(Clone)]
 struct PipelineExt {
     pipeline: miniquad::Pipeline,
@@ -332,6 +344,13 @@ impl PipelineExt {
             return;
         }
         let uniform_meta = uniform_meta.unwrap();
+        if uniform_meta.array_count > 1 {
+            warn!(
+                "Trying to set array uniform {} with a single value, use set_uniform_array",
+                name
+            );
+            return;
+        }
         let uniform_format = uniform_meta.uniform_type;
         let uniform_byte_size = uniform_format.size();
         let uniform_byte_offset = uniform_meta.byte_offset;
@@ -362,6 +381,45 @@ impl PipelineExt {
         transmute_uniform!(uniform_byte_size, uniform_byte_offset, 16);
         transmute_uniform!(uniform_byte_size, uniform_byte_offset, 64);
     }
+
+    fn set_uniform_array<T>(&mut self, name: &str, array: &[T]) {
+        let uniform_meta = self.uniforms.iter().find(
+            |Uniform {
+                 name: uniform_name, ..
+             }| uniform_name == name,
+        );
+        if uniform_meta.is_none() {
+            warn!("Trying to set non-existing uniform: {}", name);
+            return;
+        }
+        let uniform_meta = uniform_meta.unwrap();
+        let element_byte_size = uniform_meta.uniform_type.size();
+
+        if std::mem::size_of::<T>() != element_byte_size {
+            warn!(
+                "Trying to set uniform {} with {} bytes elements to {} bytes elements",
+                name,
+                element_byte_size,
+                std::mem::size_of::<T>()
+            );
+            return;
+        }
+        if array.len() > uniform_meta.array_count {
+            warn!(
+                "Trying to set uniform {} of {} elements with {} elements",
+                name,
+                uniform_meta.array_count,
+                array.len()
+            );
+            return;
+        }
+
+        let data = unsafe {
+            std::slice::from_raw_parts(array.as_ptr() as *const u8, std::mem::size_of_val(array))
+        };
+        let offset = uniform_meta.byte_offset;
+        self.uniforms_data[offset..offset + data.len()].copy_from_slice(data);
+    }
 }
 
 struct PipelinesStorage {
@@ -489,10 +547,12 @@ impl PipelinesStorage {
         let uniforms = uniforms
             .iter()
             .scan(0, |offset, uniform| {
-                let uniform_byte_size = uniform.1.size();
+                let (name, array_count) = uniform_name_and_array_count(&uniform.0);
+                let uniform_byte_size = uniform.1.size() * array_count;
                 let uniform = Uniform {
-                    name: uniform.0.clone(),
+                    name: name.to_owned(),
                     uniform_type: uniform.1,
+                    array_count,
                     byte_offset: *offset,
                 };
                 *offset += uniform_byte_size;
@@ -541,6 +601,7 @@ pub struct QuadGl {
     draw_calls_count: usize,
     state: GlState,
     start_time: f64,
+    paused_at: Option<f64>,
 
     white_texture: Texture,
     max_vertices: usize,
@@ -570,6 +631,7 @@ impl QuadGl {
             draw_calls_bindings: Vec::with_capacity(200),
             draw_calls_count: 0,
             start_time: miniquad::date::now(),
+            paused_at: None,
 
             white_texture,
             max_vertices: 10000,
@@ -589,10 +651,14 @@ impl QuadGl {
         let mut shader_meta: ShaderMeta = shader::meta();
 
         for uniform in &uniforms {
+            let (name, array_count) = uniform_name_and_array_count(&uniform.0);
+            if array_count == 0 {
+                panic!("uniform `{}` is declared as an empty array", uniform.0);
+            }
             shader_meta
                 .uniforms
                 .uniforms
-                .push(UniformDesc::new(&uniform.0, uniform.1));
+                .push(UniformDesc::new(name, uniform.1).array(array_count));
         }
 
         for texture in &textures {
@@ -635,6 +701,38 @@ impl QuadGl {
         self.clear_draw_calls();
     }
 
+    /// Seconds since start, passed to shaders as `_Time.x`.
+    /// Does not advance while paused.
+    pub fn time(&self) -> f32 {
+        let now = self.paused_at.unwrap_or_else(miniquad::date::now);
+
+        (now - self.start_time) as f32
+    }
+
+    /// Stop `_Time` in all materials, for example for a pause menu.
+    pub fn pause_time(&mut self) {
+        if self.paused_at.is_none() {
+            self.paused_at = Some(miniquad::date::now());
+        }
+    }
+
+    /// Continue `_Time` from the value it was paused at, without a jump.
+    pub fn resume_time(&mut self) {
+        if let Some(paused_at) = self.paused_at.take() {
+            self.start_time += miniquad::date::now() - paused_at;
+        }
+    }
+
+    pub fn is_time_paused(&self) -> bool {
+        self.paused_at.is_some()
+    }
+
+    /// Amount of draw calls batched since the last flush.
+    /// Flush happens at the end of the frame and when the camera changes.
+    pub fn draw_calls_count(&self) -> usize {
+        self.draw_calls_count
+    }
+
     /// Reset only draw calls state
     pub fn clear_draw_calls(&mut self) {
         self.draw_calls_count = 0;
@@ -672,7 +770,7 @@ impl QuadGl {
         assert_eq!(self.draw_calls_bindings.len(), self.draw_calls.len());
 
         let (screen_width, screen_height) = ctx.screen_size();
-        let time = (miniquad::date::now() - self.start_time) as f32;
+        let time = self.time();
         let time = glam::vec4(time, time.sin(), time.cos(), 0.);
 
         for (dc, bindings) in self.draw_calls[0..self.draw_calls_count]
@@ -772,6 +870,10 @@ impl QuadGl {
         self.state.render_pass
     }
 
+    pub fn get_active_pipeline(&self) -> Option<GlPipeline> {
+        self.state.pipeline
+    }
+
     pub fn is_depth_test_enabled(&self) -> bool {
         self.state.depth_test_enable
     }
@@ -784,6 +886,10 @@ impl QuadGl {
         self.state.depth_test_enable = enable;
     }
 
+    /// Texture for the following geometry, None for plain white.
+    /// Changing the texture, as well as the pipeline, clip, viewport, model matrix
+    /// or render pass, starts a new draw call. Grouping draws by texture,
+    /// or putting textures in an atlas, keeps the draw call count low.
     pub fn texture(&mut self, texture: Option<Texture2D>) {
         self.state.texture = texture.map_or(self.white_texture, |t| t.texture);
     }
@@ -805,6 +911,11 @@ impl QuadGl {
         ))
     }
 
+    /// Viewport set by the active camera, None for the whole render target
+    pub fn get_active_viewport(&self) -> Option<(i32, i32, i32, i32)> {
+        self.state.viewport
+    }
+
     pub fn push_model_matrix(&mut self, matrix: glam::Mat4) {
         self.state.model_stack.push(self.state.model() * matrix);
     }
@@ -824,6 +935,13 @@ impl QuadGl {
         self.state.draw_mode = mode;
     }
 
+    /// Add geometry to the current draw call.
+    ///
+    /// One draw call holds at most 10000 vertices and 5000 indices by default
+    /// (see `gl_set_drawcall_buffer_capacity`). When the current draw call is
+    /// full, a new one is started automatically, so any amount of small geometry
+    /// pieces may be drawn in one frame. A single piece of geometry bigger than
+    /// a whole draw call is clamped to the limit.
     pub fn geometry(&mut self, vertices: &[impl Into<VertexInterop> + Copy], indices: &[u16]) {
         if vertices.len() >= self.max_vertices || indices.len() >= self.max_indices {
             warn!("geometry() exceeded max drawcall size, clamping");
@@ -918,6 +1036,14 @@ impl QuadGl {
             .set_uniform(name, uniform);
     }
 
+    pub fn set_uniform_array<T>(&mut self, pipeline: GlPipeline, name: &str, array: &[T]) {
+        self.state.break_batching = true;
+
+        self.pipelines
+            .get_quad_pipeline_mut(pipeline)
+            .set_uniform_array(name, array);
+    }
+
     pub fn set_texture(&mut self, pipeline: GlPipeline, name: &str, texture: Texture2D) {
         let pipeline = self.pipelines.get_quad_pipeline_mut(pipeline);
         pipeline
@@ -936,6 +1062,11 @@ impl QuadGl {
             .or_insert(texture.texture) = texture.texture;
     }
 
+    /// Maximum amount of vertices and indices in one draw call
+    pub fn drawcall_buffer_capacity(&self) -> (usize, usize) {
+        (self.max_vertices, self.max_indices)
+    }
+
     pub(crate) fn update_drawcall_capacity(
         &mut self,
         ctx: &mut Context,
@@ -1020,3 +1151,14 @@ mod shader {
         }
     }
 }
+
+#[test]
+fn uniform_array_names() {
+    assert_eq!(uniform_name_and_array_count("Color"), ("Color", 1));
+    assert_eq!(uniform_name_and_array_count("Lights[8]"), ("Lights", 8));
+    assert_eq!(uniform_name_and_array_count("Lights[ 8 ]"), ("Lights", 8));
+    assert_eq!(uniform_name_and_array_count("Lights[0]"), ("Lights", 0));
+    // not an array size, kept as is
+    assert_eq!(uniform_name_and_array_count("Lights[n]"), ("Lights[n]", 1));
+    assert_eq!(uniform_name_and_array_count("Lights[8"), ("Lights[8", 1));
+}
@@ -47,6 +47,63 @@ pub fn draw_rectangle(x: f32, y: f32, w: f32, h: f32, color: Color) {
     context.gl.geometry(&vertices, &indices);
 }
 
+/// Draws a solid rectangle with its top-left corner at `[x, y]` with size `[w, h]`,
+/// with color changing from `top_color` to `bottom_color`.
+pub fn draw_rectangle_gradient(
+    x: f32,
+    y: f32,
+    w: f32,
+    h: f32,
+    top_color: Color,
+    bottom_color: Color,
+) {
+    draw_rectangle_gradient_ex(
+        x,
+        y,
+        w,
+        h,
+        [top_color, top_color, bottom_color, bottom_color],
+    );
+}
+
+/// Draws a solid rectangle with its top-left corner at `[x, y]` with size `[w, h]`,
+/// with color changing from `left_color` to `right_color`.
+pub fn draw_rectangle_gradient_horizontal(
+    x: f32,
+    y: f32,
+    w: f32,
+    h: f32,
+    left_color: Color,
+    right_color: Color,
+) {
+    draw_rectangle_gradient_ex(
+        x,
+        y,
+        w,
+        h,
+        [left_color, right_color, right_color, left_color],
+    );
+}
+
+/// Draws a solid rectangle with its top-left corner at `[x, y]` with size `[w, h]`,
+/// interpolating between corner colors, given clockwise from the top-left corner.
+pub fn draw_rectangle_gradient_ex(x: f32, y: f32, w: f32, h: f32, colors: [Color; 4]) {
+    let context = get_context();
+
+    #[rustfmt::skip]
+    let vertices = [
+        Vertex::new(x    , y    , 0., 0.0, 0.0, colors[0]),
+        Vertex::new(x + w, y    , 0., 1.0, 0.0, colors[1]),
+        Vertex::new(x + w, y + h, 0., 1.0, 1.0, colors[2]),
+        Vertex::new(x    , y + h, 0., 0.0, 1.0, colors[3]),
+    ];
+    let indices: [u16; 6] = [0, 1, 2, 0, 2, 3];
+
+    context.gl.texture(None);
+    context.gl.draw_mode(DrawMode::Triangles);
+    context.gl.geometry(&vertices, &indices);
+}
+
 /// Draws a rectangle outline with its top-left corner at `[x, y]` with size `[w, h]` (width going to
 /// the right, height going down), with a given line `thickness` and `color`.
 pub fn draw_rectangle_lines(x: f32, y: f32, w: f32, h: f32, thickness: f32, color: Color) {
@@ -74,6 +131,59 @@ pub fn draw_rectangle_lines(x: f32, y: f32, w: f32, h: f32, thickness: f32, colo
     context.gl.geometry(&vertices, &indices);
 }
 
+/// Draws a solid rectangle with rounded corners, top-left corner at `[x, y]` with size `[w, h]`.
+/// Each corner is an arc of `radius` made of `segments` triangles.
+/// `radius` is clamped to half of the smaller side.
+/// Negative sizes extend the rectangle to the left and up, like in [draw_rectangle].
+pub fn draw_rectangle_rounded(
+    x: f32,
+    y: f32,
+    w: f32,
+    h: f32,
+    radius: f32,
+    segments: u8,
+    color: Color,
+) {
+    if w == 0. || h == 0. {
+        return;
+    }
+    let (x, w) = if w < 0. { (x + w, -w) } else { (x, w) };
+    let (y, h) = if h < 0. { (y + h, -h) } else { (y, h) };
+
+    let context = get_context();
+
+    let r = radius.max(0.).min(w.min(h) / 2.);
+    let segments = segments.max(1);
+    let corners = [
+        (x + w - r, y + h - r),
+        (x + r, y + h - r),
+        (x + r, y + r),
+        (x + w - r, y + r),
+    ];
+
+    let mut vertices = Vec::<Vertex>::with_capacity(4 * (segments as usize + 1) + 1);
+    let mut indices = Vec::<u16>::with_capacity(4 * (segments as usize + 1) * 3);
+
+    vertices.push(Vertex::new(x + w / 2., y + h / 2., 0., 0.5, 0.5, color));
+    for (n, (cx, cy)) in corners.iter().enumerate() {
+        for i in 0..=segments {
+            let angle = (n as f32 + i as f32 / segments as f32) * std::f32::consts::FRAC_PI_2;
+            let px = cx + r * angle.cos();
+            let py = cy + r * angle.sin();
+
+            vertices.push(Vertex::new(px, py, 0., (px - x) / w, (py - y) / h, color));
+        }
+    }
+    let outline = vertices.len() as u16 - 1;
+    for i in 0..outline {
+        indices.extend_from_slice(&[0, i + 1, (i + 1) % outline + 1]);
+    }
+
+    context.gl.texture(None);
+    context.gl.draw_mode(DrawMode::Triangles);
+    context.gl.geometry(&vertices, &indices);
+}
+
 #[derive(Debug, Clone)]
 pub struct DrawRectangleParams {
     /// Adds an offset to the position
@@ -207,6 +317,32 @@ pub fn draw_circle(x: f32, y: f32, r: f32, color: Color) {
     draw_poly(x, y, 20, r, 0., color);
 }
 
+/// Draws a solid circle centered at `[x, y]` with a given radius `r`,
+/// with color changing from `inner_color` in the center to `outer_color` on the edge.
+pub fn draw_circle_gradient(x: f32, y: f32, r: f32, inner_color: Color, outer_color: Color) {
+    let context = get_context();
+    let sides = 20;
+
+    let mut vertices = Vec::<Vertex>::with_capacity(sides as usize + 2);
+    let mut indices = Vec::<u16>::with_capacity(sides as usize * 3);
+
+    vertices.push(Vertex::new(x, y, 0., 0., 0., inner_color));
+    for i in 0..sides + 1 {
+        let rx = (i as f32 / sides as f32 * std::f32::consts::PI * 2.).cos();
+        let ry = (i as f32 / sides as f32 * std::f32::consts::PI * 2.).sin();
+
+        vertices.push(Vertex::new(x + r * rx, y + r * ry, 0., rx, ry, outer_color));
+
+        if i != sides {
+            indices.extend_from_slice(&[0, i as u16 + 1, i as u16 + 2]);
+        }
+    }
+
+    context.gl.texture(None);
+    context.gl.draw_mode(DrawMode::Triangles);
+    context.gl.geometry(&vertices, &indices);
+}
+
 /// Draws a circle outline centered at `[x, y]` with a given radius, line `thickness` and `color`.
 pub fn draw_circle_lines(x: f32, y: f32, r: f32, thickness: f32, color: Color) {
     draw_poly_lines(x, y, 20, r, 0., thickness, color);
@@ -242,3 +378,48 @@ pub fn draw_line(x1: f32, y1: f32, x2: f32, y2: f32, thickness: f32, color: Colo
         &[0, 1, 2, 2, 1, 3],
     );
 }
+
+/// Draws a line between points `[x1, y1]` and `[x2, y2]` with a given `thickness` and `color`,
+/// with edges fading out over one more unit on both sides.
+/// With the default camera the unit is a pixel, which smooths the edges without MSAA.
+/// Line ends are not smoothed.
+pub fn draw_line_aa(x1: f32, y1: f32, x2: f32, y2: f32, thickness: f32, color: Color) {
+    let context = get_context();
+    let dx = x2 - x1;
+    let dy = y2 - y1;
+
+    let len = (dx * dx + dy * dy).sqrt();
+    if len < f32::EPSILON {
+        return;
+    }
+    // unit normal
+    let nx = -dy / len;
+    let ny = dx / len;
+
+    let t = thickness * 0.5;
+    let f = t + 1.;
+    let transparent = Color::new(color.r, color.g, color.b, 0.);
+
+    #[rustfmt::skip]
+    let indices: [u16; 18] = [
+        0, 1, 4, 4, 1, 5,
+        1, 2, 5, 5, 2, 6,
+        2, 3, 6, 6, 3, 7,
+    ];
+
+    context.gl.texture(None);
+    context.gl.draw_mode(DrawMode::Triangles);
+    context.gl.geometry(
+        &[
+            Vertex::new(x1 + nx * f, y1 + ny * f, 0., 0., 0., transparent),
+            Vertex::new(x1 + nx * t, y1 + ny * t, 0., 0., 0., color),
+            Vertex::new(x1 - nx * t, y1 - ny * t, 0., 0., 0., color),
+            Vertex::new(x1 - nx * f, y1 - ny * f, 0., 0., 0., transparent),
+            Vertex::new(x2 + nx * f, y2 + ny * f, 0., 0., 0., transparent),
+            Vertex::new(x2 + nx * t, y2 + ny * t, 0., 0., 0., color),
+            Vertex::new(x2 - nx * t, y2 - ny * t, 0., 0., 0., color),
+            Vertex::new(x2 - nx * f, y2 - ny * f, 0., 0., 0., transparent),
+        ],
+        &indices,
+    );
+}
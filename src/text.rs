@@ -9,7 +9,7 @@ use crate::{
     texture::Image,
 };
 
-use crate::color::WHITE;
+use crate::color::{BLACK, WHITE};
 use glam::vec2;
 
 use std::cell::RefCell;
@@ -226,6 +226,11 @@ pub struct TextParams {
     /// Default is 0.0
     pub rotation: f32,
     pub color: Color,
+    /// Outline around the glyphs, in the same units as text position,
+    /// for legibility over busy backgrounds.
+    /// Default is 0.0, no outline
+    pub outline_thickness: f32,
+    pub outline_color: Color,
 }
 
 impl Default for TextParams {
@@ -237,6 +242,8 @@ impl Default for TextParams {
             font_scale_aspect: 1.0,
             color: WHITE,
             rotation: 0.0,
+            outline_thickness: 0.0,
+            outline_color: BLACK,
         }
     }
 }
@@ -287,6 +294,27 @@ pub fn draw_text(text: &str, x: f32, y: f32, font_size: f32, color: Color) {
 
 /// Draw text with custom params such as font, font size and font scale.
 pub fn draw_text_ex(text: &str, x: f32, y: f32, params: TextParams) {
+    if params.outline_thickness > 0. {
+        // outline is the text itself, drawn shifted in 8 directions behind the text
+        let t = params.outline_thickness;
+        for &(dx, dy) in &[
+            (-t, -t),
+            (0., -t),
+            (t, -t),
+            (-t, 0.),
+            (t, 0.),
+            (-t, t),
+            (0., t),
+            (t, t),
+        ] {
+            draw_text_glyphs(text, x + dx, y + dy, &params, params.outline_color);
+        }
+    }
+
+    draw_text_glyphs(text, x, y, &params, params.color);
+}
+
+fn draw_text_glyphs(text: &str, x: f32, y: f32, params: &TextParams, color: Color) {
     let font = get_context().fonts_storage.get_font_mut(params.font);
 
     let font_scale_x = params.font_scale * params.font_scale_aspect;
@@ -331,7 +359,7 @@ pub fn draw_text_ex(text: &str, x: f32, y: f32, params: TextParams) {
             atlas.texture(),
             dest.x,
             dest.y,
-            params.color,
+            color,
             crate::texture::DrawTextureParams {
                 dest_size: Some(vec2(dest.w, dest.h)),
                 source: Some(source),
@@ -4,13 +4,14 @@ use crate::{
     color::Color,
     file::{load_file, FileError},
     get_context, get_quad_context,
-    math::Rect,
+    math::{Rect, RectOffset},
 };
 
 use crate::quad_gl::{DrawMode, Vertex};
 use glam::{vec2, Vec2};
 
 pub use crate::quad_gl::FilterMode;
+pub use miniquad::TextureWrap;
 
 /// Image, data stored in CPU memory
 #[derive(Clone)]
@@ -96,6 +97,40 @@ impl Image {
         }
     }
 
+    /// Creates an Image with a checkerboard of `cell_size` pixel squares,
+    /// starting with `color_a` in the top left corner.
+    ///
+    /// Handy as a placeholder for missing textures or to check UV mapping.
+    pub fn gen_image_checkerboard(
+        width: u16,
+        height: u16,
+        cell_size: u16,
+        color_a: Color,
+        color_b: Color,
+    ) -> Image {
+        let cell_size = cell_size.max(1) as usize;
+        let color_a: [u8; 4] = color_a.into();
+        let color_b: [u8; 4] = color_b.into();
+
+        let mut bytes = vec![0; width as usize * height as usize * 4];
+        for y in 0..height as usize {
+            for x in 0..width as usize {
+                let color = if (x / cell_size + y / cell_size) & 1 == 0 {
+                    color_a
+                } else {
+                    color_b
+                };
+                let i = (y * width as usize + x) * 4;
+                bytes[i..i + 4].copy_from_slice(&color);
+            }
+        }
+        Image {
+            width,
+            height,
+            bytes,
+        }
+    }
+
     /// Updates this image from a slice of [Color]s.
     pub fn update(&mut self, colors: &[Color]) {
         assert!(self.width as usize * self.height as usize == colors.len());
@@ -231,7 +266,34 @@ impl RenderTarget {
     }
 }
 
+/// Params for [render_target_ex]
+#[derive(Debug, Clone, Copy)]
+pub struct RenderTargetParams {
+    /// Filtering of the target texture when it is drawn, Linear by default.
+    /// Nearest keeps low resolution pixel art targets crisp.
+    pub filter: FilterMode,
+    /// Add a depth buffer to the target, needed for depth testing
+    /// when drawing with a `Camera3D` into the target. False by default.
+    pub depth: bool,
+}
+
+impl Default for RenderTargetParams {
+    fn default() -> RenderTargetParams {
+        RenderTargetParams {
+            filter: FilterMode::Linear,
+            depth: false,
+        }
+    }
+}
+
 pub fn render_target(width: u32, height: u32) -> RenderTarget {
+    render_target_ex(width, height, Default::default())
+}
+
+/// Create a render target with a filter mode and, optionally, a depth buffer.
+///
+/// Multisampled render targets are not supported.
+pub fn render_target_ex(width: u32, height: u32, params: RenderTargetParams) -> RenderTarget {
     let context = get_quad_context();
 
     let texture = miniquad::Texture::new_render_texture(
@@ -239,11 +301,26 @@ pub fn render_target(width: u32, height: u32) -> RenderTarget {
         miniquad::TextureParams {
             width,
             height,
+            filter: params.filter,
             ..Default::default()
         },
     );
 
-    let render_pass = miniquad::RenderPass::new(context, texture, None);
+    let depth_texture = if params.depth {
+        Some(miniquad::Texture::new_render_texture(
+            context,
+            miniquad::TextureParams {
+                width,
+                height,
+                format: miniquad::TextureFormat::Depth,
+                ..Default::default()
+            },
+        ))
+    } else {
+        None
+    };
+
+    let render_pass = miniquad::RenderPass::new(context, texture, depth_texture);
 
     let texture = Texture2D::from_miniquad_texture(texture);
 
@@ -253,6 +330,24 @@ pub fn render_target(width: u32, height: u32) -> RenderTarget {
     }
 }
 
+/// Fill the whole render target with a solid color,
+/// without making it the active one.
+///
+/// Useful to reset history buffers in ping-pong post processing.
+pub fn clear_render_target(render_target: &RenderTarget, color: Color) {
+    let context = get_context();
+
+    // draw calls already made into this target should be behind the clear
+    context.perform_render_passes();
+
+    let ctx = get_quad_context();
+    ctx.begin_pass(
+        render_target.render_pass,
+        miniquad::PassAction::clear_color(color.r, color.g, color.b, color.a),
+    );
+    ctx.end_render_pass();
+}
+
 #[derive(Debug, Clone)]
 pub struct DrawTextureParams {
     pub dest_size: Option<Vec2>,
@@ -384,6 +479,91 @@ pub fn draw_texture_ex(
     context.gl.geometry(&vertices, &indices);
 }
 
+/// Draw a texture as a nine-slice (nine-patch) panel covering `dest`.
+/// `border` is in texture pixels. Corners are drawn unscaled, edges are
+/// stretched along one axis and the center along both.
+/// If `dest` is too small for the borders, they are shrunk proportionally.
+pub fn draw_texture_9slice(texture: Texture2D, dest: Rect, border: RectOffset, color: Color) {
+    let source = Rect::new(0., 0., texture.width(), texture.height());
+
+    for (src, dst) in nine_slice_rects(source, dest, border).iter() {
+        if src.w <= 0. || src.h <= 0. || dst.w <= 0. || dst.h <= 0. {
+            continue;
+        }
+        draw_texture_ex(
+            texture,
+            dst.x,
+            dst.y,
+            color,
+            DrawTextureParams {
+                dest_size: Some(vec2(dst.w, dst.h)),
+                source: Some(*src),
+                ..Default::default()
+            },
+        );
+    }
+}
+
+/// Source and destination rects of the nine slices, row by row from the top left.
+fn nine_slice_rects(source: Rect, dest: Rect, border: RectOffset) -> [(Rect, Rect); 9] {
+    let fit = |a: f32, b: f32, size: f32| {
+        if a + b > size && a + b > 0. {
+            let k = size / (a + b);
+            (a * k, b * k)
+        } else {
+            (a, b)
+        }
+    };
+    let (left, right) = fit(border.left, border.right, dest.w);
+    let (top, bottom) = fit(border.top, border.bottom, dest.h);
+
+    let sxs = [
+        source.x,
+        source.x + border.left,
+        source.x + source.w - border.right,
+        source.x + source.w,
+    ];
+    let sys = [
+        source.y,
+        source.y + border.top,
+        source.y + source.h - border.bottom,
+        source.y + source.h,
+    ];
+    let dxs = [
+        dest.x,
+        dest.x + left,
+        dest.x + dest.w - right,
+        dest.x + dest.w,
+    ];
+    let dys = [
+        dest.y,
+        dest.y + top,
+        dest.y + dest.h - bottom,
+        dest.y + dest.h,
+    ];
+
+    let mut rects = [(Rect::new(0., 0., 0., 0.), Rect::new(0., 0., 0., 0.)); 9];
+    for row in 0..3 {
+        for column in 0..3 {
+            rects[row * 3 + column] = (
+                Rect::new(
+                    sxs[column],
+                    sys[row],
+                    sxs[column + 1] - sxs[column],
+                    sys[row + 1] - sys[row],
+                ),
+                Rect::new(
+                    dxs[column],
+                    dys[row],
+                    dxs[column + 1] - dxs[column],
+                    dys[row + 1] - dys[row],
+                ),
+            );
+        }
+    }
+    rects
+}
+
 #[deprecated(since = "0.3.0", note = "Use draw_texture_ex instead")]
 pub fn draw_texture_rec(
     texture: Texture2D,
@@ -586,6 +766,16 @@ impl Texture2D {
         self.texture.set_filter(ctx, filter_mode);
     }
 
+    /// Sets the [TextureWrap] of this texture, how UVs outside of 0..1 are sampled.
+    ///
+    /// Use Repeat for tiling textures, like ground in 3D meshes.
+    /// Note that on WebGL1 only power of two textures support Repeat and Mirror.
+    pub fn set_wrap(&self, wrap: TextureWrap) {
+        let ctx = get_quad_context();
+
+        self.texture.set_wrap(ctx, wrap);
+    }
+
     /// Returns the handle for this texture.
     pub fn raw_miniquad_texture_handle(&self) -> miniquad::Texture {
         self.texture
@@ -678,3 +868,59 @@ pub fn build_textures_atlas() {
     let texture = context.texture_batcher.atlas.texture();
     crate::telemetry::log_string(&format!("Atlas: {} {}", texture.width(), texture.height()));
 }
+
+#[test]
+fn nine_slice() {
+    let source = Rect::new(0., 0., 32., 32.);
+    let dest = Rect::new(10., 20., 100., 50.);
+    let rects = nine_slice_rects(source, dest, RectOffset::new(4., 8., 2., 6.));
+
+    // corners keep their size
+    assert_eq!(rects[0].0, Rect::new(0., 0., 4., 2.));
+    assert_eq!(rects[0].1, Rect::new(10., 20., 4., 2.));
+    assert_eq!(rects[8].0, Rect::new(24., 26., 8., 6.));
+    assert_eq!(rects[8].1, Rect::new(102., 64., 8., 6.));
+    // center stretches
+    assert_eq!(rects[4].0, Rect::new(4., 2., 20., 24.));
+    assert_eq!(rects[4].1, Rect::new(14., 22., 88., 42.));
+
+    // borders larger than dest are shrunk to fit
+    let rects = nine_slice_rects(
+        source,
+        Rect::new(0., 0., 6., 8.),
+        RectOffset::new(4., 8., 2., 6.),
+    );
+    assert_eq!(rects[0].1, Rect::new(0., 0., 2., 2.));
+    assert_eq!(rects[1].1.w, 0.);
+    assert_eq!(rects[2].1, Rect::new(2., 0., 4., 2.));
+}
+
+#[test]
+fn image_checkerboard() {
+    use crate::color::{BLACK, WHITE};
+
+    let image = Image::gen_image_checkerboard(5, 3, 2, WHITE, BLACK);
+    assert_eq!(image.bytes.len(), 5 * 3 * 4);
+    let rows: Vec<String> = (0..3)
+        .map(|y| {
+            (0..5)
+                .map(|x| {
+                    if image.get_pixel(x, y) == WHITE {
+                        'a'
+                    } else {
+                        'b'
+                    }
+                })
+                .collect()
+        })
+        .collect();
+    assert_eq!(rows, vec!["aabba", "aabba", "bbaab"]);
+
+    // zero cell size is one pixel cells
+    let image = Image::gen_image_checkerboard(3, 2, 0, WHITE, BLACK);
+    assert_eq!(image.get_pixel(0, 0), WHITE);
+    assert_eq!(image.get_pixel(1, 0), BLACK);
+    assert_eq!(image.get_pixel(0, 1), BLACK);
+    assert_eq!(image.get_pixel(2, 1), BLACK);
+    assert_eq!(image.get_pixel(1, 1), WHITE);
+}
@@ -32,3 +32,20 @@ pub fn get_time() -> f64 {
 
     miniquad::date::now() - context.start_time
 }
+
+/// Freezes the `_Time` uniform of all materials, see [resume_shader_time].
+/// Does not affect [get_time] and [get_frame_time].
+pub fn pause_shader_time() {
+    get_context().gl.pause_time();
+}
+
+/// Continues the `_Time` uniform from where [pause_shader_time] stopped it,
+/// so animated materials do not jump on resume.
+pub fn resume_shader_time() {
+    get_context().gl.resume_time();
+}
+
+/// Current value of the `_Time.x` shader uniform, in seconds.
+pub fn get_shader_time() -> f32 {
+    get_context().gl.time()
+}
@@ -23,7 +23,19 @@ pub fn clear_background(color: Color) {
     context.gl.clear(get_quad_context(), color);
 }
 
-#[doc(hidden)]
+/// Set the color screen is cleared with at the beginning of every frame,
+/// black by default.
+/// Saves calling `clear_background` with the same color each frame.
+pub fn set_default_background_color(color: Color) {
+    get_context().background_color = color;
+}
+
+/// Set the maximum amount of vertices and indices in one draw call.
+///
+/// Default is 10000 vertices and 5000 indices.
+/// Bigger draw calls means less draw calls for big, heavily batched scenes
+/// (maps, charts), at the cost of more memory for each draw call buffer.
+/// Draw call buffers are recreated, so better call it once, before drawing anything.
 pub fn gl_set_drawcall_buffer_capacity(max_vertices: usize, max_indices: usize) {
     let context = get_context();
     context
@@ -31,6 +43,22 @@ pub fn gl_set_drawcall_buffer_capacity(max_vertices: usize, max_indices: usize)
         .update_drawcall_capacity(get_quad_context(), max_vertices, max_indices);
 }
 
+/// Current maximum amount of vertices and indices in one draw call,
+/// see [gl_set_drawcall_buffer_capacity].
+pub fn gl_get_drawcall_buffer_capacity() -> (usize, usize) {
+    let context = get_context();
+
+    context.gl.drawcall_buffer_capacity()
+}
+
+/// Amount of draw calls batched so far, since the frame start or the last camera change.
+/// Useful to check how well draws are batched, see `QuadGl::texture`.
+pub fn gl_get_draw_calls_count() -> usize {
+    let context = get_context();
+
+    context.gl.draw_calls_count()
+}
+
 pub struct InternalGlContext<'a> {
     pub quad_context: &'a mut miniquad::Context,
     pub quad_gl: &'a mut crate::quad_gl::QuadGl,
@@ -39,6 +67,9 @@ pub struct InternalGlContext<'a> {
 impl<'a> InternalGlContext<'a> {
     /// Draw all the batched stuff and reset the internal state cache
     /// May be helpful for combining macroquad's drawing with raw miniquad/opengl calls
+    ///
+    /// Flushing is not required to avoid overflowing the batcher: full draw calls
+    /// are split automatically, see `QuadGl::geometry`.
     pub fn flush(&mut self) {
         get_context().perform_render_passes();
     }
@@ -65,6 +96,16 @@ pub fn screen_height() -> f32 {
     context.screen_height / get_quad_context().dpi_scale()
 }
 
+/// Amount of physical pixels in one logical pixel, 1.0 on regular displays
+/// and usually 2.0 on high-DPI ones (with `high_dpi` in the window [Conf]).
+///
+/// Almost everything in macroquad works in logical pixels: `screen_width`, `screen_height`,
+/// `mouse_position` and camera `world_to_screen`/`screen_to_world`.
+/// Camera viewports and render targets are in physical pixels.
+pub fn screen_dpi_scale() -> f32 {
+    get_quad_context().dpi_scale()
+}
+
 /// Request the window size to be the given value. This takes DPI into account.
 ///
 /// Note that the OS might decide to give a different size. Additionally, the size in macroquad won't be updated until the next `next_frame().await`.